@@ -4,6 +4,7 @@ use binoxxo::rules::{is_board_full, is_board_valid};
 use std::str::FromStr;
 use std::string::ToString;
 
+#[allow(clippy::to_string_in_format_args)]
 fn print_board_with_check(board: &Board) {
     println!(
         "Board:\n\n{} --> is full:  {}\n     is valid: {}",
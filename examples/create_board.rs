@@ -1,6 +1,7 @@
 //! This example demonstrates how to create a puzzle with `create_puzzle_board`.
 //! It also shows how to print a board to terminal.
 
+#[allow(clippy::to_string_in_format_args)]
 fn main() {
     // create a puzzle
     let size = 10usize;
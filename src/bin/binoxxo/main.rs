@@ -0,0 +1,568 @@
+//! Command-line tool to generate, solve, check, grade and explain
+//! binoxxo puzzles.
+//!
+//! Boards are read from stdin with `Board::from_str` and written to stdout
+//! with `Board::to_string`: one row per line with `X`, `O` and `_`
+//! separated by spaces, given clues with a `*` suffix (e.g. `X*`). Several
+//! boards are separated by empty lines. `--notation` reads and writes a
+//! `Notation` instead, which also allows `#` comments.
+//!
+//! `binoxxo book` lays out the puzzles from stdin as printable HTML book;
+//! every puzzle must have exactly one solution.
+//!
+//! Run `binoxxo help` for the list of subcommands and flags.
+//! The interactive game `binoxxo play` needs the `tui` feature.
+
+#[cfg(feature = "tui")]
+mod play;
+
+use binoxxo::book::{Book, Puzzle};
+use binoxxo::bruteforce::{count_solutions, rng_from_seed, solve, BoardKind, Budget, Generator};
+use binoxxo::field::{Board, Field, ParseBoardError};
+use binoxxo::logic::{
+    grade_details, next_hint, try_create_puzzle_board_with_difficulty_with_rng, Difficulty, Hint,
+};
+use binoxxo::notation::Notation;
+use binoxxo::rules::{check_board, is_board_full};
+
+use rand::{thread_rng, RngCore};
+use serde_json::{json, Value};
+
+use std::env;
+use std::error::Error;
+use std::io::{self, Read};
+use std::process;
+use std::str::FromStr;
+
+const USAGE: &str = "\
+Usage: binoxxo <command> [flags]
+
+Commands:
+  generate   create new puzzles and write them to stdout
+  solve      solve the boards from stdin
+  check      report the broken rules of the boards from stdin
+  grade      grade the difficulty of the puzzles from stdin
+  hint       explain the next move of the boards from stdin
+  book       write the puzzles from stdin as printable HTML book with an
+             answer key (each puzzle needs exactly one solution)
+  play       play a puzzle in the terminal (needs the tui feature)
+  help       print this help
+
+Flags:
+  --size <n>          side length of generated boards (default: 10)
+  --width <n>         number of columns of generated boards
+  --height <n>        number of rows of generated boards
+  --seed <n>          seed for reproducible boards
+  --count <n>         number of generated boards (default: 1)
+  --clues <n>         stop taking fields away at <n> clues (default: 0)
+  --guesses <n>       create puzzles, which may have several solutions
+                      (play default: 15)
+  --difficulty <d>    create puzzles of difficulty easy, medium, hard or expert;
+                      only square boards, because the difficulty generator
+                      takes a single side length (use --size)
+  --full              create full boards instead of puzzles
+  --format <f>        output format: text (default) or json
+  --notation <n>      read and write boards in notation binoxxo (X O _),
+                      takuzu (0 1 .) or dense (XO__ without spaces) instead
+                      of the format of Board::from_str; # starts a comment
+  --title <t>         title of the book (default: Binoxxo)
+
+Exit status: 0 on success, 1 if a board breaks a rule, has no solution
+or no hint, 2 on invalid arguments or input.
+";
+
+/// Exit code for boards, which break a rule or have no solution.
+const EXIT_FAILURE: i32 = 1;
+/// Exit code for invalid arguments or input.
+const EXIT_USAGE: i32 = 2;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Command {
+    Generate,
+    Solve,
+    Check,
+    Grade,
+    Hint,
+    Book,
+    Play,
+    Help,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+/// Kind of generated boards.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Kind {
+    Full,
+    Guesses(usize),
+    Unique { clues: usize },
+    Difficulty(Difficulty),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Options {
+    width: usize,
+    height: usize,
+    seed: Option<u64>,
+    count: usize,
+    /// Kind given on the command line, each command has its own default.
+    kind: Option<Kind>,
+    format: Format,
+    notation: Option<Notation>,
+    title: Option<String>,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            width: 10,
+            height: 10,
+            seed: None,
+            count: 1,
+            kind: None,
+            format: Format::Text,
+            notation: None,
+            title: None,
+        }
+    }
+}
+
+/// Result of a command: its output and whether all boards passed.
+struct Outcome {
+    output: String,
+    success: bool,
+}
+
+fn parse_command(name: &str) -> Result<Command, String> {
+    match name {
+        "generate" => Ok(Command::Generate),
+        "solve" => Ok(Command::Solve),
+        "check" => Ok(Command::Check),
+        "grade" => Ok(Command::Grade),
+        "hint" => Ok(Command::Hint),
+        "book" => Ok(Command::Book),
+        "play" => Ok(Command::Play),
+        "help" | "--help" | "-h" => Ok(Command::Help),
+        _ => Err(format!("unknown command '{}'", name)),
+    }
+}
+
+fn parse_number<T: FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} expects a number, got '{}'", flag, value))
+}
+
+fn parse_difficulty(value: &str) -> Result<Difficulty, String> {
+    Difficulty::ALL
+        .iter()
+        .copied()
+        .find(|difficulty| difficulty.name() == value)
+        .ok_or_else(|| format!("unknown difficulty '{}'", value))
+}
+
+fn set_kind(kind: &mut Option<Kind>, new_kind: Kind) -> Result<(), String> {
+    match kind {
+        Some(_) => Err("only one of --full, --guesses, --clues and --difficulty is allowed".into()),
+        None => {
+            *kind = Some(new_kind);
+            Ok(())
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<(Command, Options), String> {
+    let command = match args.first() {
+        Some(name) => parse_command(name)?,
+        None => return Err("missing command".into()),
+    };
+    let mut options = Options::default();
+    let mut size = None;
+    let mut width = None;
+    let mut height = None;
+
+    let mut args = args[1..].iter();
+    while let Some(flag) = args.next() {
+        if "--full" == flag {
+            set_kind(&mut options.kind, Kind::Full)?;
+            continue;
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("{} expects a value", flag))?;
+        match flag.as_str() {
+            "--size" => size = Some(parse_number(flag, value)?),
+            "--width" => width = Some(parse_number(flag, value)?),
+            "--height" => height = Some(parse_number(flag, value)?),
+            "--seed" => options.seed = Some(parse_number(flag, value)?),
+            "--count" => options.count = parse_number(flag, value)?,
+            "--clues" => set_kind(
+                &mut options.kind,
+                Kind::Unique {
+                    clues: parse_number(flag, value)?,
+                },
+            )?,
+            "--guesses" => set_kind(&mut options.kind, Kind::Guesses(parse_number(flag, value)?))?,
+            "--difficulty" => set_kind(
+                &mut options.kind,
+                Kind::Difficulty(parse_difficulty(value)?),
+            )?,
+            "--notation" => {
+                options.notation = Some(match value.as_str() {
+                    "binoxxo" => Notation::binoxxo(),
+                    "takuzu" => Notation::takuzu(),
+                    "dense" => Notation::dense(),
+                    _ => return Err(format!("unknown notation '{}'", value)),
+                })
+            }
+            "--title" => options.title = Some(value.clone()),
+            "--format" => {
+                options.format = match value.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    _ => return Err(format!("unknown format '{}'", value)),
+                }
+            }
+            _ => return Err(format!("unknown flag '{}'", flag)),
+        }
+    }
+
+    let size = size.unwrap_or(options.width);
+    options.width = width.unwrap_or(size);
+    options.height = height.unwrap_or(size);
+    Ok((command, options))
+}
+
+/// Returns whether `line` is a comment in notation `notation`.
+fn is_comment(line: &str, notation: Option<&Notation>) -> bool {
+    notation
+        .and_then(|notation| notation.comment)
+        .is_some_and(|comment| line.trim_start().starts_with(comment))
+}
+
+/// Parses `text` with `Board::from_str` or in notation `notation`.
+fn parse_board(text: &str, notation: Option<&Notation>) -> Result<Board, ParseBoardError> {
+    match notation {
+        Some(notation) => notation.parse(text),
+        None => Board::from_str(text),
+    }
+}
+
+/// Formats `board` with `Board::to_string` or in notation `notation`.
+fn format_board(board: &Board, notation: Option<&Notation>) -> String {
+    match notation {
+        Some(notation) => notation.format(board),
+        None => board.to_string(),
+    }
+}
+
+/// Splits `input` at empty lines into boards written in notation `notation`.
+/// Blocks with only comments are skipped.
+fn parse_boards(input: &str, notation: Option<&Notation>) -> Result<Vec<Board>, String> {
+    let mut boards = Vec::new();
+    let mut text = String::new();
+    let mut has_fields = false;
+    for line in input.lines().chain(std::iter::once("")) {
+        if line.trim().is_empty() {
+            if has_fields {
+                let board = parse_board(&text, notation)
+                    .map_err(|error| format!("board {}: {}", boards.len() + 1, error))?;
+                boards.push(board);
+            }
+            text.clear();
+            has_fields = false;
+        } else {
+            has_fields |= !is_comment(line, notation);
+            text.push_str(line);
+            text.push('\n');
+        }
+    }
+    if boards.is_empty() {
+        return Err("no board on stdin".into());
+    }
+    Ok(boards)
+}
+
+/// Returns the rows of `board` as JSON array of strings.
+fn json_board(board: &Board) -> Value {
+    board.to_string().lines().collect()
+}
+
+/// Writes the JSON values `values` as pretty printed JSON array.
+fn json_array(values: Vec<Value>) -> String {
+    let mut output = serde_json::to_string_pretty(&Value::Array(values))
+        .expect("JSON values are always serializable");
+    output.push('\n');
+    output
+}
+
+/// Creates one board of kind `options.kind`, by default a unique puzzle.
+fn generate_board(
+    options: &Options,
+    budget: &Budget,
+    rng: &mut dyn RngCore,
+) -> Result<Board, Box<dyn Error>> {
+    let (width, height) = (options.width, options.height);
+    let kind = match options.kind.unwrap_or(Kind::Unique { clues: 0 }) {
+        Kind::Full => BoardKind::Full,
+        Kind::Guesses(guesses) => BoardKind::Puzzle { guesses },
+        Kind::Unique { clues } => BoardKind::Unique { clues },
+        Kind::Difficulty(difficulty) => {
+            if width != height {
+                return Err("--difficulty only creates square boards, use --size".into());
+            }
+            let board =
+                try_create_puzzle_board_with_difficulty_with_rng(width, difficulty, budget, rng)?;
+            return Ok(board);
+        }
+    };
+    let generator = Generator::new(width, height, kind).with_budget(*budget);
+    Ok(generator.build_with_rng(rng)?)
+}
+
+fn generate(options: &Options) -> Result<Outcome, String> {
+    let mut rng: Box<dyn RngCore> = match options.seed {
+        Some(seed) => Box::new(rng_from_seed(seed)),
+        None => Box::new(thread_rng()),
+    };
+    let budget = Budget::for_dimensions(options.width, options.height);
+
+    let mut boards = Vec::new();
+    for _ in 0..options.count {
+        let board = generate_board(options, &budget, rng.as_mut());
+        boards.push(board.map_err(|error| error.to_string())?);
+    }
+
+    let output = match options.format {
+        Format::Text => boards
+            .iter()
+            .map(|board| format_board(board, options.notation.as_ref()))
+            .collect::<Vec<String>>()
+            .join("\n"),
+        Format::Json => json_array(
+            boards
+                .iter()
+                .map(|board| json!({ "board": json_board(board) }))
+                .collect(),
+        ),
+    };
+    Ok(Outcome {
+        output,
+        success: true,
+    })
+}
+
+/// Runs `f` for every board and joins the outputs.
+/// `f` returns the text output, the JSON object and whether the board passed.
+fn for_each_board<F: Fn(&Board) -> (String, Value, bool)>(
+    boards: &[Board],
+    format: Format,
+    f: F,
+) -> Outcome {
+    let results = boards.iter().map(f).collect::<Vec<(String, Value, bool)>>();
+    let success = results.iter().all(|(_, _, success)| *success);
+    let output = match format {
+        Format::Text => results
+            .into_iter()
+            .map(|(text, _, _)| text)
+            .collect::<Vec<String>>()
+            .join("\n"),
+        Format::Json => json_array(results.into_iter().map(|(_, json, _)| json).collect()),
+    };
+    Outcome { output, success }
+}
+
+fn solve_board(board: &Board, notation: Option<&Notation>) -> (String, Value, bool) {
+    match solve(board) {
+        Some(solution) => {
+            let unique = 1 == count_solutions(board, 2);
+            let text = if unique {
+                format_board(&solution, notation)
+            } else {
+                format!("{}(not unique)\n", format_board(&solution, notation))
+            };
+            let json = json!({ "solution": json_board(&solution), "unique": unique });
+            (text, json, true)
+        }
+        None => (
+            "no solution\n".into(),
+            json!({ "solution": null, "unique": false }),
+            false,
+        ),
+    }
+}
+
+fn check(board: &Board) -> (String, Value, bool) {
+    let violations = check_board(board);
+    let full = is_board_full(board);
+    let text = if !violations.is_empty() {
+        violations
+            .iter()
+            .map(|violation| format!("{}\n", violation))
+            .collect()
+    } else if full {
+        "valid\n".into()
+    } else {
+        "no broken rules, but not full\n".into()
+    };
+    let json = json!({
+        "full": full,
+        "valid": full && violations.is_empty(),
+        "violations": violations
+            .iter()
+            .map(|violation| violation.to_string())
+            .collect::<Vec<String>>(),
+    });
+    (text, json, violations.is_empty())
+}
+
+fn grade_board(board: &Board) -> (String, Value, bool) {
+    match grade_details(board) {
+        Some(grade) => {
+            let technique = grade.hardest_technique.map(|technique| technique.name());
+            let text = format!(
+                "{} (score {}, hardest technique {}, {} guesses)\n",
+                grade.difficulty,
+                grade.score,
+                technique.unwrap_or("none"),
+                grade.guesses
+            );
+            let json = json!({
+                "difficulty": grade.difficulty.name(),
+                "score": grade.score,
+                "hardest_technique": technique,
+                "guesses": grade.guesses,
+            });
+            (text, json, true)
+        }
+        None => ("no solution\n".into(), json!({ "difficulty": null }), false),
+    }
+}
+
+fn hint(board: &Board) -> (String, Value, bool) {
+    match next_hint(board) {
+        Some(hint) => {
+            let (x, y) = hint.position();
+            let field = match &hint {
+                Hint::Forced { step, .. } => step.field,
+                Hint::Violation { .. } => Field::Empty,
+            };
+            let text = format!("column {}, row {}: {}\n", x + 1, y + 1, hint.reason());
+            let json = json!({
+                "column": x + 1,
+                "row": y + 1,
+                "field": match field {
+                    Field::Empty => None,
+                    field => Some(field.to_string()),
+                },
+                "reason": hint.reason(),
+            });
+            // a hint pointing at a broken rule is no move
+            (text, json, Field::Empty != field)
+        }
+        None => ("no hint\n".into(), Value::Null, false),
+    }
+}
+
+/// Writes the puzzles `boards` as HTML book with title `title`.
+fn book(boards: Vec<Board>, title: Option<&String>) -> Result<Outcome, String> {
+    let mut puzzles = Vec::new();
+    for (index, board) in boards.into_iter().enumerate() {
+        let number = index + 1;
+        let puzzle = Puzzle::new(format!("Puzzle {}", number), board)
+            .ok_or_else(|| format!("board {}: no unique solution", number))?;
+        puzzles.push(puzzle);
+    }
+    let mut book = Book::default();
+    if let Some(title) = title {
+        book.title = title.clone();
+    }
+    Ok(Outcome {
+        output: book.to_html(&puzzles),
+        success: true,
+    })
+}
+
+/// Number of guesses of the puzzles of `binoxxo play`.
+#[cfg(feature = "tui")]
+const PLAY_GUESSES: usize = 15;
+
+#[cfg(feature = "tui")]
+fn play_game(options: &Options) -> Result<Outcome, String> {
+    if options.width != options.height {
+        return Err("play needs a square board".into());
+    }
+    let guesses = match options.kind {
+        Some(Kind::Guesses(guesses)) => guesses,
+        None => PLAY_GUESSES,
+        Some(_) => return Err("play only supports --guesses".into()),
+    };
+    play::play(options.width, guesses, options.seed)?;
+    Ok(Outcome {
+        output: String::new(),
+        success: true,
+    })
+}
+
+#[cfg(not(feature = "tui"))]
+fn play_game(_options: &Options) -> Result<Outcome, String> {
+    Err("play is not available, build binoxxo with --features tui".into())
+}
+
+fn run(command: Command, options: &Options, input: &str) -> Result<Outcome, String> {
+    let solve = |board: &Board| solve_board(board, options.notation.as_ref());
+    let command: &dyn Fn(&Board) -> (String, Value, bool) = match command {
+        Command::Help => {
+            return Ok(Outcome {
+                output: USAGE.into(),
+                success: true,
+            })
+        }
+        Command::Generate => return generate(options),
+        Command::Play => return play_game(options),
+        Command::Book => {
+            let boards = parse_boards(input, options.notation.as_ref())?;
+            return book(boards, options.title.as_ref());
+        }
+        Command::Solve => &solve,
+        Command::Check => &check,
+        Command::Grade => &grade_board,
+        Command::Hint => &hint,
+    };
+    let boards = parse_boards(input, options.notation.as_ref())?;
+    Ok(for_each_board(&boards, options.format, command))
+}
+
+fn read_stdin(command: Command) -> Result<String, String> {
+    let mut input = String::new();
+    if ![Command::Generate, Command::Play, Command::Help].contains(&command) {
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|error| format!("cannot read stdin: {}", error))?;
+    }
+    Ok(input)
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+    let result = parse_args(&args).and_then(|(command, options)| {
+        let input = read_stdin(command)?;
+        run(command, &options, &input)
+    });
+    match result {
+        Ok(outcome) => {
+            print!("{}", outcome.output);
+            if !outcome.success {
+                process::exit(EXIT_FAILURE);
+            }
+        }
+        Err(error) => {
+            eprintln!("binoxxo: {}\n\n{}", error, USAGE);
+            process::exit(EXIT_USAGE);
+        }
+    }
+}
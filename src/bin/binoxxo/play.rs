@@ -0,0 +1,321 @@
+//! Interactive terminal game: `binoxxo play`.
+//!
+//! The game state (`Game`) is independent from the terminal, the
+//! terminal is only used by `play` to draw the board and to read keys.
+
+use binoxxo::bruteforce::{create_puzzle_board, create_puzzle_board_with_rng, rng_from_seed};
+use binoxxo::field::{Board, Field};
+use binoxxo::rules::{check_board, is_board_full, is_board_valid};
+use binoxxo::session::GameSession;
+
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{read, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{
+    Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor,
+};
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use crossterm::{execute, queue};
+
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+const KEYS: &str = "arrows/hjkl: move  x/o: set  space: clear  u: undo  r: redo  \
+                    a: candidates  n: new game  q: quit";
+
+/// A game: the session of the player and the cursor.
+struct Game {
+    session: GameSession,
+    cursor: (usize, usize),
+}
+
+impl Game {
+    fn new(puzzle: Board) -> Game {
+        Game {
+            session: GameSession::new(puzzle),
+            cursor: (0, 0),
+        }
+    }
+
+    fn board(&self) -> &Board {
+        self.session.board()
+    }
+
+    fn size(&self) -> usize {
+        self.board().get_width()
+    }
+
+    /// Moves the cursor by `dx` columns and `dy` rows (wrapping around).
+    fn move_cursor(&mut self, dx: isize, dy: isize) {
+        let size = self.size() as isize;
+        let (x, y) = self.cursor;
+        self.cursor = (
+            (x as isize + dx).rem_euclid(size) as usize,
+            (y as isize + dy).rem_euclid(size) as usize,
+        );
+    }
+
+    /// Sets the field under the cursor to `field` (`Empty` clears it).
+    /// Clues cannot be changed.
+    fn enter(&mut self, field: Field) {
+        let (x, y) = self.cursor;
+        // the session rejects changes of given fields, nothing to report
+        self.session.set(x, y, field).ok();
+    }
+
+    /// Takes back the last change and moves the cursor to its field.
+    fn undo(&mut self) {
+        if let Some(change) = self.session.undo() {
+            self.cursor = (change.x, change.y);
+        }
+    }
+
+    /// Applies the last undone change again and moves the cursor to its field.
+    fn redo(&mut self) {
+        if let Some(change) = self.session.redo() {
+            self.cursor = (change.x, change.y);
+        }
+    }
+
+    /// Returns all fields, which are part of a broken rule.
+    fn violations(&self) -> HashSet<(usize, usize)> {
+        check_board(self.board())
+            .iter()
+            .flat_map(|violation| violation.positions(self.board()))
+            .collect()
+    }
+
+    fn is_won(&self) -> bool {
+        is_board_full(self.board()) && is_board_valid(self.board())
+    }
+}
+
+fn new_puzzle(size: usize, guesses: usize, seed: Option<u64>) -> Board {
+    match seed {
+        Some(seed) => create_puzzle_board_with_rng(size, guesses, &mut rng_from_seed(seed)),
+        None => create_puzzle_board(size, guesses),
+    }
+}
+
+/// Returns the candidates of an empty field as text of a field.
+fn candidates_text(candidates: &[Field]) -> &'static str {
+    match candidates {
+        [Field::X, Field::O] => "x o",
+        [Field::X] => " x ",
+        [Field::O] => " o ",
+        _ => " . ",
+    }
+}
+
+fn draw<W: Write>(out: &mut W, game: &Game) -> io::Result<()> {
+    queue!(out, Clear(ClearType::All), MoveTo(0, 0), Print("binoxxo"))?;
+    let violations = game.violations();
+    let size = game.size();
+    for y in 0..size {
+        queue!(out, MoveTo(2, 2 + y as u16))?;
+        for x in 0..size {
+            let field = game.board().get(x, y);
+            if game.board().is_given(x, y) {
+                queue!(
+                    out,
+                    SetAttribute(Attribute::Bold),
+                    SetForegroundColor(Color::White)
+                )?;
+            } else {
+                queue!(out, SetForegroundColor(Color::Cyan))?;
+            }
+            if violations.contains(&(x, y)) {
+                queue!(out, SetBackgroundColor(Color::DarkRed))?;
+            }
+            if game.cursor == (x, y) {
+                queue!(out, SetAttribute(Attribute::Reverse))?;
+            }
+            let text = match field {
+                Field::Empty => candidates_text(&game.session.notes().candidates(x, y)).into(),
+                field => format!(" {} ", field),
+            };
+            queue!(out, Print(text), SetAttribute(Attribute::Reset), ResetColor)?;
+        }
+    }
+    let status = if game.is_won() {
+        "Solved! Press n for a new game or q to quit."
+    } else if !violations.is_empty() {
+        "Some fields break a rule."
+    } else {
+        ""
+    };
+    queue!(
+        out,
+        MoveTo(0, 3 + size as u16),
+        Print(status),
+        MoveTo(0, 5 + size as u16),
+        Print(KEYS)
+    )?;
+    out.flush()
+}
+
+fn event_loop<W: Write>(
+    out: &mut W,
+    size: usize,
+    guesses: usize,
+    seed: Option<u64>,
+) -> io::Result<()> {
+    let mut game = Game::new(new_puzzle(size, guesses, seed));
+    loop {
+        draw(out, &game)?;
+        let key = match read()? {
+            Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind: KeyEventKind::Press,
+                ..
+            }) => (code, modifiers),
+            _ => continue,
+        };
+        match key {
+            (KeyCode::Char('q'), _) | (KeyCode::Esc, _) => return Ok(()),
+            (KeyCode::Char('c'), KeyModifiers::CONTROL) => return Ok(()),
+            (KeyCode::Left, _) | (KeyCode::Char('h'), _) => game.move_cursor(-1, 0),
+            (KeyCode::Right, _) | (KeyCode::Char('l'), _) => game.move_cursor(1, 0),
+            (KeyCode::Up, _) | (KeyCode::Char('k'), _) => game.move_cursor(0, -1),
+            (KeyCode::Down, _) | (KeyCode::Char('j'), _) => game.move_cursor(0, 1),
+            (KeyCode::Char('x'), _) | (KeyCode::Char('X'), _) => game.enter(Field::X),
+            (KeyCode::Char('o'), _) | (KeyCode::Char('O'), _) => game.enter(Field::O),
+            (KeyCode::Char(' '), _) | (KeyCode::Backspace, _) | (KeyCode::Delete, _) => {
+                game.enter(Field::Empty)
+            }
+            (KeyCode::Char('u'), _) => game.undo(),
+            (KeyCode::Char('r'), _) => game.redo(),
+            (KeyCode::Char('a'), _) => game.session.fill_candidates(),
+            // a new game with the same seed would be the same game
+            (KeyCode::Char('n'), _) => game = Game::new(new_puzzle(size, guesses, None)),
+            _ => (),
+        }
+    }
+}
+
+/// Plays games on a board of side length `size` until the player quits.
+pub fn play(size: usize, guesses: usize, seed: Option<u64>) -> Result<(), String> {
+    let mut out = io::stdout();
+    let result = enable_raw_mode()
+        .and_then(|_| execute!(out, EnterAlternateScreen, Hide))
+        .and_then(|_| event_loop(&mut out, size, guesses, seed));
+    // restore the terminal even if the game failed
+    let restored = execute!(out, Show, LeaveAlternateScreen).and_then(|_| disable_raw_mode());
+    result
+        .and(restored)
+        .map_err(|error| format!("terminal error: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn game() -> Game {
+        Game::new(
+            Board::from_str(
+                "
+                X _ _ _
+                _ _ O _
+                _ _ _ _
+                _ O _ _",
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn clues_are_locked() {
+        let mut game = game();
+
+        game.enter(Field::O);
+        game.enter(Field::Empty);
+
+        assert_eq!(Field::X, game.board().get(0, 0));
+        assert!(game.session.history().is_empty());
+    }
+
+    #[test]
+    fn enter_and_undo() {
+        let mut game = game();
+        game.move_cursor(1, 0);
+
+        game.enter(Field::X);
+        game.enter(Field::O);
+        game.move_cursor(0, 1);
+        game.undo();
+
+        assert_eq!(Field::X, game.board().get(1, 0));
+        assert_eq!((1, 0), game.cursor);
+
+        game.undo();
+        game.undo();
+
+        assert_eq!(Field::Empty, game.board().get(1, 0));
+
+        game.move_cursor(1, 1);
+        game.redo();
+
+        assert_eq!(Field::X, game.board().get(1, 0));
+        assert_eq!((1, 0), game.cursor);
+    }
+
+    #[test]
+    fn cursor_wraps_around() {
+        let mut game = game();
+
+        game.move_cursor(-1, -1);
+
+        assert_eq!((3, 3), game.cursor);
+    }
+
+    #[test]
+    fn violations_are_highlighted() {
+        let mut game = game();
+        game.move_cursor(1, 0);
+        game.enter(Field::X);
+        game.move_cursor(1, 0);
+        game.enter(Field::X);
+
+        let violations = game.violations();
+
+        assert!(violations.contains(&(0, 0)));
+        assert!(violations.contains(&(2, 0)));
+        assert!(!violations.contains(&(0, 1)));
+    }
+
+    #[test]
+    fn win() {
+        let solution = "X O X O\nO X O X\nX X O O\nO O X X";
+        let mut game = Game::new(Board::from_str(&solution.replacen("X", "_", 1)).unwrap());
+        assert!(!game.is_won());
+
+        game.enter(Field::X);
+
+        assert!(game.is_won());
+    }
+
+    #[test]
+    fn shows_candidates() {
+        let mut game = game();
+        game.move_cursor(1, 0);
+        game.enter(Field::X);
+
+        game.session.fill_candidates();
+
+        assert_eq!(
+            " o ",
+            candidates_text(&game.session.notes().candidates(2, 0))
+        );
+        assert_eq!(
+            "x o",
+            candidates_text(&game.session.notes().candidates(0, 2))
+        );
+        assert_eq!(
+            " . ",
+            candidates_text(&game.session.notes().candidates(0, 0))
+        );
+    }
+}
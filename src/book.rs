@@ -0,0 +1,371 @@
+//! This module lays out puzzles as printable booklet:
+//! `struct` [`Book`](struct.Book.html).
+//!
+//! A book is a self-contained HTML page (no scripts, no external files)
+//! with several grids per printed page, a title and difficulty label per
+//! puzzle and an answer key at the back. Print it from a browser or
+//! convert it to PDF with the "print to PDF" function of the browser.
+//!
+//! ```
+//! use binoxxo::book::{Book, Puzzle};
+//! let puzzles = (1..=4)
+//!     .map(|number| {
+//!         let board = binoxxo::bruteforce::create_unique_puzzle_board(6, 0);
+//!         Puzzle::new(format!("Puzzle {}", number), board).unwrap()
+//!     })
+//!     .collect::<Vec<Puzzle>>();
+//! let book = Book {
+//!     title: "Weekly binoxxo".to_string(),
+//!     ..Book::default()
+//! };
+//! let html = book.to_html(&puzzles);
+//! assert!(html.starts_with("<!DOCTYPE html>"));
+//! ```
+
+use crate::bruteforce::{has_unique_solution, solve};
+use crate::field::Board;
+use crate::logic::{grade, Difficulty};
+use crate::svg::{escape, Svg};
+
+use std::fmt::{self, Write};
+
+/// A puzzle of a book with its solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Puzzle {
+    /// title above the grid and in the answer key
+    pub title: String,
+    /// the puzzle; its given fields are drawn as clues
+    pub board: Board,
+    /// the solution of the puzzle for the answer key
+    pub solution: Board,
+    /// difficulty label, `None` for no label
+    pub difficulty: Option<Difficulty>,
+}
+
+impl Puzzle {
+    /// Creates the puzzle `board` with title `title`.
+    /// All filled fields of `board` become clues (see
+    /// [`Board::mark_given`](../field/struct.Board.html#method.mark_given)).
+    /// The solution and the difficulty are calculated.
+    ///
+    /// Returns `None` unless `board` has exactly one solution. Otherwise a
+    /// correct answer of a reader could differ from the answer key.
+    /// Puzzles from
+    /// [`create_puzzle_board`](../bruteforce/build_board/fn.create_puzzle_board.html)
+    /// often have several solutions, use
+    /// [`create_unique_puzzle_board`](../bruteforce/build_board/fn.create_unique_puzzle_board.html)
+    /// instead.
+    pub fn new<S: Into<String>>(title: S, mut board: Board) -> Option<Puzzle> {
+        board.mark_given();
+        if !has_unique_solution(&board) {
+            return None;
+        }
+        let solution = solve(&board)?;
+        let difficulty = grade(&board);
+        Some(Puzzle {
+            title: title.into(),
+            board,
+            solution,
+            difficulty: Some(difficulty),
+        })
+    }
+}
+
+/// Layout of a printable book.
+///
+/// Lengths are in millimeters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Book {
+    /// title at the top of every page
+    pub title: String,
+    /// heading of the answer key
+    pub answers_title: String,
+    /// paper width
+    pub page_width: f64,
+    /// paper height
+    pub page_height: f64,
+    /// margin on all sides of the paper
+    pub margin: f64,
+    /// number of puzzles side by side
+    pub columns: usize,
+    /// number of puzzles one below the other
+    pub rows: usize,
+    /// number of solutions side by side in the answer key
+    pub answer_columns: usize,
+    /// number of solutions one below the other in the answer key
+    pub answer_rows: usize,
+    /// style of the grids
+    pub svg: Svg,
+}
+
+/// A4 paper with 2 x 3 puzzles and 3 x 4 solutions per page.
+impl Default for Book {
+    fn default() -> Book {
+        Book {
+            title: "Binoxxo".to_string(),
+            answers_title: "Solutions".to_string(),
+            page_width: 210.0,
+            page_height: 297.0,
+            margin: 12.0,
+            columns: 2,
+            rows: 3,
+            answer_columns: 3,
+            answer_rows: 4,
+            svg: Svg::default(),
+        }
+    }
+}
+
+impl Book {
+    /// Returns the number of printed pages of a book with `count` puzzles:
+    /// the puzzle pages and the pages of the answer key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a page has room for no puzzle.
+    pub fn page_count(&self, count: usize) -> (usize, usize) {
+        let per_page = self.per_page();
+        let answers_per_page = self.answers_per_page();
+        (
+            (count + per_page - 1) / per_page,
+            (count + answers_per_page - 1) / answers_per_page,
+        )
+    }
+
+    fn per_page(&self) -> usize {
+        let per_page = self.columns * self.rows;
+        assert!(0 < per_page, "a page needs room for a puzzle");
+        per_page
+    }
+
+    fn answers_per_page(&self) -> usize {
+        let per_page = self.answer_columns * self.answer_rows;
+        assert!(0 < per_page, "a page needs room for a solution");
+        per_page
+    }
+
+    /// Writes the puzzles `puzzles` and their answer key as HTML page.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a page has room for no puzzle.
+    pub fn to_html(&self, puzzles: &[Puzzle]) -> String {
+        let mut html = String::new();
+        self.write(&mut html, puzzles)
+            .expect("writing to a String cannot fail");
+        html
+    }
+
+    fn write(&self, html: &mut String, puzzles: &[Puzzle]) -> fmt::Result {
+        let title = escape(&self.title);
+        writeln!(html, "<!DOCTYPE html>")?;
+        writeln!(html, "<html>\n<head>\n<meta charset=\"utf-8\">")?;
+        writeln!(html, "<title>{}</title>", title)?;
+        self.write_style(html)?;
+        writeln!(html, "</head>\n<body>")?;
+        for page in puzzles.chunks(self.per_page()) {
+            self.write_page(html, &title, self.columns, self.rows, page, false)?;
+        }
+        let answers_title = format!("{} &ndash; {}", title, escape(&self.answers_title));
+        for page in puzzles.chunks(self.answers_per_page()) {
+            self.write_page(
+                html,
+                &answers_title,
+                self.answer_columns,
+                self.answer_rows,
+                page,
+                true,
+            )?;
+        }
+        writeln!(html, "</body>\n</html>")
+    }
+
+    fn write_style(&self, html: &mut String) -> fmt::Result {
+        writeln!(html, "<style>")?;
+        writeln!(
+            html,
+            "@page {{ size: {}mm {}mm; margin: {}mm; }}",
+            self.page_width, self.page_height, self.margin
+        )?;
+        writeln!(html, "body {{ margin: 0; font-family: sans-serif; }}")?;
+        // slightly smaller than the printable area against rounding errors
+        writeln!(
+            html,
+            ".page {{ display: flex; flex-direction: column; box-sizing: border-box; \
+             width: {}mm; height: {}mm; break-after: page; overflow: hidden; }}",
+            self.page_width - 2.0 * self.margin,
+            self.page_height - 2.0 * self.margin - 1.0
+        )?;
+        writeln!(
+            html,
+            ".page h1 {{ margin: 0 0 4mm 0; font-size: 14pt; text-align: center; }}"
+        )?;
+        writeln!(
+            html,
+            ".grids {{ flex: 1; min-height: 0; display: grid; gap: 5mm; }}"
+        )?;
+        writeln!(
+            html,
+            "figure {{ margin: 0; min-height: 0; display: flex; flex-direction: column; }}"
+        )?;
+        writeln!(
+            html,
+            "figcaption {{ margin-bottom: 1mm; font-size: 11pt; }}\n\
+             .difficulty {{ float: right; color: #606060; font-size: 9pt; }}"
+        )?;
+        writeln!(
+            html,
+            "figure svg {{ flex: 1; min-height: 0; width: 100%; height: auto; }}"
+        )?;
+        writeln!(html, "</style>")
+    }
+
+    fn write_page(
+        &self,
+        html: &mut String,
+        title: &str,
+        columns: usize,
+        rows: usize,
+        puzzles: &[Puzzle],
+        answers: bool,
+    ) -> fmt::Result {
+        writeln!(html, "<section class=\"page\">\n<h1>{}</h1>", title)?;
+        writeln!(
+            html,
+            "<div class=\"grids\" style=\"grid-template-columns: repeat({}, 1fr); \
+             grid-template-rows: repeat({}, 1fr);\">",
+            columns, rows
+        )?;
+        for puzzle in puzzles {
+            write!(html, "<figure>\n<figcaption>{}", escape(&puzzle.title))?;
+            if let Some(difficulty) = puzzle.difficulty.filter(|_| !answers) {
+                write!(html, " <span class=\"difficulty\">{}</span>", difficulty)?;
+            }
+            writeln!(html, "</figcaption>")?;
+            if answers {
+                html.push_str(
+                    &self
+                        .svg
+                        .render_with_solution(&puzzle.board, &puzzle.solution),
+                );
+            } else {
+                html.push_str(&self.svg.render(&puzzle.board));
+            }
+            writeln!(html, "</figure>")?;
+        }
+        writeln!(html, "</div>\n</section>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const PUZZLE: &str = "
+        O O _ _
+        O _ _ _
+        _ _ X _
+        _ _ _ _";
+
+    fn puzzles(count: usize) -> Vec<Puzzle> {
+        (1..=count)
+            .map(|number| {
+                Puzzle::new(format!("No. {}", number), Board::from_str(PUZZLE).unwrap()).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn new_puzzle() {
+        let puzzle = puzzles(1).remove(0);
+
+        assert!(puzzle.board.is_given(0, 0));
+        assert!(!puzzle.board.is_given(1, 1));
+        assert_eq!(
+            Board::from_str("O* O* X X\nO* X O X\nX O X* O\nX X O O").unwrap(),
+            puzzle.solution
+        );
+        assert!(puzzle.difficulty.is_some());
+    }
+
+    #[test]
+    fn puzzle_without_solution() {
+        assert_eq!(None, Puzzle::new("", Board::from_str("X X\n_ _").unwrap()));
+    }
+
+    #[test]
+    fn puzzle_with_several_solutions() {
+        assert_eq!(
+            None,
+            Puzzle::new(
+                "",
+                Board::from_str("X _ _ _ _ _ _ _ _ _ _ _ _ _ _ _").unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn page_count() {
+        let book = Book::default();
+
+        assert_eq!((0, 0), book.page_count(0));
+        assert_eq!((1, 1), book.page_count(6));
+        assert_eq!((3, 2), book.page_count(13));
+    }
+
+    #[test]
+    fn to_html() {
+        let book = Book {
+            title: "Fish & Chips".to_string(),
+            ..Book::default()
+        };
+
+        let html = book.to_html(&puzzles(7));
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.ends_with("</html>\n"));
+        assert!(html.contains("<title>Fish &amp; Chips</title>"));
+        // 2 puzzle pages and 1 page of solutions
+        assert_eq!(3, html.matches("<section class=\"page\">").count());
+        assert_eq!(14, html.matches("<svg").count());
+        assert_eq!(7, html.matches("class=\"difficulty\"").count());
+        assert!(html.contains("<h1>Fish &amp; Chips &ndash; Solutions</h1>"));
+        assert!(html.contains("@page { size: 210mm 297mm; margin: 12mm; }"));
+    }
+
+    #[test]
+    fn answer_key_shows_solutions() {
+        let book = Book {
+            answer_columns: 1,
+            answer_rows: 1,
+            ..Book::default()
+        };
+
+        let html = book.to_html(&puzzles(2));
+        let answers = html.split("Solutions</h1>").nth(1).unwrap();
+
+        // the 12 empty fields of the puzzle are filled in
+        assert_eq!(
+            12,
+            answers
+                .split("</section>")
+                .next()
+                .unwrap()
+                .matches(&format!("fill=\"{}\"", book.svg.solution.color))
+                .count()
+        );
+        assert_eq!(2, html.matches("Solutions</h1>").count());
+    }
+
+    #[test]
+    #[should_panic(expected = "a page needs room for a puzzle")]
+    fn page_without_room() {
+        let book = Book {
+            rows: 0,
+            ..Book::default()
+        };
+
+        book.to_html(&puzzles(1));
+    }
+}
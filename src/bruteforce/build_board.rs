@@ -4,36 +4,131 @@
 //! returns the resulting incomplete board as puzzle.
 //! Because the board was constructed from a valid board, there exists
 //! at least one valid solution for the board.
+//!
+//! `fn` [`create_unique_puzzle_board`](fn.create_unique_puzzle_board.html)
+//! takes fields only away, as long as the puzzle keeps exactly one solution.
+//! `fn` [`create_puzzle_board_with_difficulty`](../../logic/generate/fn.create_puzzle_board_with_difficulty.html)
+//! in the `logic` module additionally keeps the puzzle within a difficulty level.
+//!
+//! The `create_*` functions create square boards and panic on invalid
+//! sizes or if the search fails. A [`Generator`](struct.Generator.html)
+//! also creates rectangular boards (e.g. 8 x 10), returns a
+//! [`BuildError`](enum.BuildError.html) instead and limits the search
+//! with a [`Budget`](struct.Budget.html).
 
-use crate::bruteforce::choose_move::{select_next_move, Move, MoveSelection};
-use crate::bruteforce::possible_move::calc_possible_moves;
+use crate::bruteforce::choose_move::{select_next_move_with_rng, Move, MoveSelection};
+use crate::bruteforce::incremental::IncrementalBoard;
+use crate::bruteforce::possible_move::calc_incremental_possible_moves;
+use crate::bruteforce::random::{gen_index, shuffle};
+use crate::bruteforce::solver::has_unique_solution;
 use crate::field::Board;
 
 use rand::{thread_rng, Rng};
 
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Limits the search of the puzzle generator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Budget {
+    /// maximal number of moves (including moves, which are undone later)
+    pub max_tries: usize,
+    /// maximal wall-clock time of the search (no limit if `None`);
+    /// a zero timeout stops the search before the first move
+    pub timeout: Option<Duration>,
+}
+
+impl Budget {
+    /// Returns the budget used by the `create_*` functions for boards of
+    /// side length `size`: `size * size * 100` tries and no timeout.
+    pub fn for_size(size: usize) -> Budget {
+        Budget::for_dimensions(size, size)
+    }
+
+    /// Returns the default budget of a [`Generator`](struct.Generator.html)
+    /// for boards with `width` columns and `height` rows:
+    /// `width * height * 100` tries (at most `usize::MAX`) and no timeout.
+    pub fn for_dimensions(width: usize, height: usize) -> Budget {
+        Budget {
+            max_tries: width.saturating_mul(height).saturating_mul(100),
+            timeout: None,
+        }
+    }
+
+    fn check_timeout(&self, start: Instant) -> Result<(), BuildError> {
+        match self.timeout {
+            Some(timeout) if start.elapsed() >= timeout => Err(BuildError::Timeout(timeout)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Reasons why the puzzle generator did not return a board.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BuildError {
+    /// board size (width or height) is odd or zero
+    InvalidSize(usize),
+    /// no valid board was found within the given number of tries
+    SearchExhausted {
+        /// number of tries of the search
+        tries: usize,
+    },
+    /// no valid board was found within the given time
+    Timeout(Duration),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::InvalidSize(size) => {
+                write!(f, "board size {} must be even and larger than zero", size)
+            }
+            BuildError::SearchExhausted { tries } => {
+                write!(f, "no board found after {} tries", tries)
+            }
+            BuildError::Timeout(timeout) => write!(f, "no board found within {:?}", timeout),
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+fn check_size(size: usize) -> Result<(), BuildError> {
+    if 0 < size && 0 == size % 2 {
+        Ok(())
+    } else {
+        Err(BuildError::InvalidSize(size))
+    }
+}
+
+fn check_dimensions(width: usize, height: usize) -> Result<(), BuildError> {
+    check_size(width)?;
+    check_size(height)
+}
+
 struct Game {
-    board: Board,
+    board: IncrementalBoard,
     moves: Vec<Move>,
 }
 
 impl Game {
-    pub fn new(size: usize) -> Game {
+    pub fn new(width: usize, height: usize) -> Game {
         Game {
-            board: Board::new(size),
+            board: IncrementalBoard::new(width, height),
             moves: Vec::new(),
         }
     }
 
     pub fn is_full(&self) -> bool {
-        let size = self.board.get_size();
-        self.moves.len() == (size * size)
+        self.board.is_full()
     }
 
-    fn new_move(&mut self) -> bool {
+    fn new_move<R: Rng + ?Sized>(&mut self, rng: &mut R) -> bool {
         assert!(!self.is_full());
 
-        let possible_moves = calc_possible_moves(&mut self.board);
-        if let Some(m) = select_next_move(&possible_moves) {
+        let possible_moves = calc_incremental_possible_moves(&mut self.board);
+        if let Some(m) = select_next_move_with_rng(&possible_moves, rng) {
             self.board.set(m.x, m.y, m.field);
             self.moves.push(m);
             true
@@ -51,36 +146,57 @@ impl Game {
         }
     }
 
-    fn build_full_game(size: usize, max_tries: usize) -> Option<Game> {
-        let mut game = Game::new(size);
-        let mut rng = thread_rng();
+    fn build_full_game<R: Rng + ?Sized>(
+        (width, height): (usize, usize),
+        budget: &Budget,
+        start: Instant,
+        rng: &mut R,
+    ) -> Result<Game, BuildError> {
+        check_dimensions(width, height)?;
+        let mut game = Game::new(width, height);
 
-        for _ in 0..max_tries {
+        for _ in 0..budget.max_tries {
             if game.is_full() {
-                return Some(game);
+                return Ok(game);
             }
-            if !game.new_move() {
+            budget.check_timeout(start)?;
+            if !game.new_move(rng) {
                 let max = game.moves.len();
-                let number_of_moves = rng.gen_range(1..max);
+                let number_of_moves = 1 + gen_index(rng, max - 1);
                 game.undo_moves(number_of_moves);
             }
         }
 
         if game.is_full() {
-            Some(game)
+            Ok(game)
         } else {
-            None
+            Err(BuildError::SearchExhausted {
+                tries: budget.max_tries,
+            })
         }
     }
 
-    pub fn build_full_board(size: usize, max_tries: usize) -> Option<Board> {
-        Some(Game::build_full_game(size, max_tries)?.board)
+    pub fn build_full_board<R: Rng + ?Sized>(
+        dimensions: (usize, usize),
+        budget: &Budget,
+        rng: &mut R,
+    ) -> Result<Board, BuildError> {
+        Ok(
+            Game::build_full_game(dimensions, budget, Instant::now(), rng)?
+                .board
+                .into_board(),
+        )
     }
 
-    pub fn build_puzzle_board(size: usize, max_tries: usize, guesses: usize) -> Option<Board> {
-        let game = Game::build_full_game(size, max_tries)?;
+    pub fn build_puzzle_board<R: Rng + ?Sized>(
+        dimensions: (usize, usize),
+        budget: &Budget,
+        guesses: usize,
+        rng: &mut R,
+    ) -> Result<Board, BuildError> {
+        let game = Game::build_full_game(dimensions, budget, Instant::now(), rng)?;
 
-        let mut board = game.board;
+        let mut board = game.board.into_board();
         let mut moves = game.moves;
         let mut guesses = guesses;
 
@@ -97,7 +213,119 @@ impl Game {
             }
         }
 
-        Some(board)
+        Ok(board)
+    }
+
+    pub fn build_unique_puzzle_board<R: Rng + ?Sized>(
+        dimensions: (usize, usize),
+        budget: &Budget,
+        clues: usize,
+        rng: &mut R,
+    ) -> Result<Board, BuildError> {
+        let start = Instant::now();
+        let board = Game::build_full_game(dimensions, budget, start, rng)?
+            .board
+            .into_board();
+
+        take_away_fields(board, clues, budget, start, rng, has_unique_solution)
+    }
+}
+
+/// Kinds of boards created by a [`Generator`](struct.Generator.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BoardKind {
+    /// a valid and full board
+    /// (see `fn` [`create_full_board`](fn.create_full_board.html))
+    Full,
+    /// a puzzle with at least one solution
+    /// (see `fn` [`create_puzzle_board`](fn.create_puzzle_board.html))
+    Puzzle {
+        /// number of random moves kept on the board
+        guesses: usize,
+    },
+    /// a puzzle with exactly one solution
+    /// (see `fn` [`create_unique_puzzle_board`](fn.create_unique_puzzle_board.html))
+    Unique {
+        /// number of filled fields, at which taking fields away stops
+        clues: usize,
+    },
+}
+
+/// Options of the puzzle generator: dimensions and kind of the board and
+/// the budget of the search.
+///
+/// Unlike the `create_*` functions, `build` returns a
+/// [`BuildError`](enum.BuildError.html) instead of panicking:
+///
+/// ```
+/// use binoxxo::bruteforce::{rng_from_seed, BoardKind, Budget, Generator};
+/// use std::time::Duration;
+/// let generator = Generator::new(8, 10, BoardKind::Unique { clues: 0 }).with_budget(Budget {
+///     max_tries: 100_000,
+///     timeout: Some(Duration::from_secs(1)),
+/// });
+/// match generator.build_with_rng(&mut rng_from_seed(4711)) {
+///     Ok(board) => println!("Board:\n{}", board),
+///     Err(error) => println!("No board: {}", error),
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Generator {
+    /// number of columns
+    pub width: usize,
+    /// number of rows
+    pub height: usize,
+    /// kind of the board
+    pub kind: BoardKind,
+    /// limits of the search
+    pub budget: Budget,
+}
+
+impl Generator {
+    /// Returns a generator of boards of kind `kind` with `width` columns
+    /// and `height` rows and the budget
+    /// [`Budget::for_dimensions`](struct.Budget.html#method.for_dimensions).
+    pub fn new(width: usize, height: usize, kind: BoardKind) -> Generator {
+        Generator {
+            width,
+            height,
+            kind,
+            budget: Budget::for_dimensions(width, height),
+        }
+    }
+
+    /// Returns a generator of square boards of side length `size`
+    /// (see `fn` [`new`](#method.new)).
+    pub fn square(size: usize, kind: BoardKind) -> Generator {
+        Generator::new(size, size, kind)
+    }
+
+    /// Returns the generator with the budget `budget`.
+    pub fn with_budget(self, budget: Budget) -> Generator {
+        Generator { budget, ..self }
+    }
+
+    /// Returns a new board or an error if width or height is invalid or
+    /// the search exceeds the budget.
+    ///
+    /// The timeout of the budget also limits taking away the fields.
+    pub fn build(&self) -> Result<Board, BuildError> {
+        self.build_with_rng(&mut thread_rng())
+    }
+
+    /// Same as `fn` [`build`](#method.build), but takes all random choices
+    /// from `rng`.
+    pub fn build_with_rng<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Board, BuildError> {
+        let dimensions = (self.width, self.height);
+        match self.kind {
+            BoardKind::Full => Game::build_full_board(dimensions, &self.budget, rng),
+            BoardKind::Puzzle { guesses } => {
+                Game::build_puzzle_board(dimensions, &self.budget, guesses, rng)
+            }
+            BoardKind::Unique { clues } => {
+                Game::build_unique_puzzle_board(dimensions, &self.budget, clues, rng)
+            }
+        }
     }
 }
 
@@ -112,13 +340,25 @@ impl Game {
 /// algorithm. This however leads to the small chance, that the algorithm does
 /// not find a valid board in the limited number of tries.
 /// No such panic was yet discovered while testing.
+///
+/// Use a [`Generator`](struct.Generator.html) to get an error instead.
 pub fn create_full_board(size: usize) -> Board {
-    let max_tries = size * size * 100;
-    if let Some(board) = Game::build_full_board(size, max_tries) {
-        return board;
-    }
+    create_full_board_with_rng(size, &mut thread_rng())
+}
 
-    panic!("No board found for size {} after {} tries", size, max_tries);
+/// Same as `fn` [`create_full_board`](fn.create_full_board.html), but takes
+/// all random choices from `rng`.
+/// Hence, the same seeded `rng` always creates the same board
+/// (see `fn` [`rng_from_seed`](../random/fn.rng_from_seed.html)).
+///
+/// # Panics
+///
+/// See `fn` [`create_full_board`](fn.create_full_board.html).
+pub fn create_full_board_with_rng<R: Rng + ?Sized>(size: usize, rng: &mut R) -> Board {
+    match Generator::square(size, BoardKind::Full).build_with_rng(rng) {
+        Ok(board) => board,
+        Err(error) => panic!("No board found for size {}: {}", size, error),
+    }
 }
 
 /// Returns a binoxxo puzzle board of side length `size`.
@@ -132,13 +372,325 @@ pub fn create_full_board(size: usize) -> Board {
 /// May also panic if it didn't find a valid board.
 /// See `fn` [`create_full_board`](fn.create_full_board.html) for details.
 pub fn create_puzzle_board(size: usize, guesses: usize) -> Board {
-    let max_tries = size * size * 100;
-    if let Some(board) = Game::build_puzzle_board(size, max_tries, guesses) {
-        return board;
+    create_puzzle_board_with_rng(size, guesses, &mut thread_rng())
+}
+
+/// Same as `fn` [`create_puzzle_board`](fn.create_puzzle_board.html), but takes
+/// all random choices from `rng`.
+///
+/// # Panics
+///
+/// See `fn` [`create_puzzle_board`](fn.create_puzzle_board.html).
+pub fn create_puzzle_board_with_rng<R: Rng + ?Sized>(
+    size: usize,
+    guesses: usize,
+    rng: &mut R,
+) -> Board {
+    match Generator::square(size, BoardKind::Puzzle { guesses }).build_with_rng(rng) {
+        Ok(board) => board,
+        Err(error) => panic!(
+            "No board found for size {} with {} guesses: {}",
+            size, guesses, error
+        ),
     }
+}
+
+/// Returns a binoxxo puzzle board of side length `size`, which has
+/// exactly one valid solution.
+///
+/// Starting from a full board, fields are taken away (set to `Empty`)
+/// in random order, as long as the puzzle keeps a unique solution.
+/// It stops as soon as only `clues` non-empty fields are left or
+/// no more field can be taken away.
+/// Use `0` for `clues` to take away as many fields as possible.
+///
+/// # Panics
+///
+/// Panics if `size` is odd or zero.
+///
+/// May also panic if it didn't find a valid board.
+/// See `fn` [`create_full_board`](fn.create_full_board.html) for details.
+pub fn create_unique_puzzle_board(size: usize, clues: usize) -> Board {
+    create_unique_puzzle_board_with_rng(size, clues, &mut thread_rng())
+}
 
-    panic!(
-        "No board found for size {} with {} guesses after {} tries",
-        size, guesses, max_tries
-    );
+/// Same as `fn` [`create_unique_puzzle_board`](fn.create_unique_puzzle_board.html),
+/// but takes all random choices from `rng`.
+///
+/// # Panics
+///
+/// See `fn` [`create_unique_puzzle_board`](fn.create_unique_puzzle_board.html).
+pub fn create_unique_puzzle_board_with_rng<R: Rng + ?Sized>(
+    size: usize,
+    clues: usize,
+    rng: &mut R,
+) -> Board {
+    match Generator::square(size, BoardKind::Unique { clues }).build_with_rng(rng) {
+        Ok(board) => board,
+        Err(error) => panic!(
+            "No board found for size {} with {} clues: {}",
+            size, clues, error
+        ),
+    }
+}
+
+/// Returns a valid and full board with `width` columns and `height` rows.
+///
+/// The timeout of `budget` is measured from `start`, so that several
+/// searches can share one budget.
+pub(crate) fn build_full_board_since<R: Rng + ?Sized>(
+    dimensions: (usize, usize),
+    budget: &Budget,
+    start: Instant,
+    rng: &mut R,
+) -> Result<Board, BuildError> {
+    Ok(Game::build_full_game(dimensions, budget, start, rng)?
+        .board
+        .into_board())
+}
+
+/// Takes fields of `board` away (in random order) as long as
+/// `is_valid_puzzle` accepts the resulting board and more than `clues`
+/// fields are left.
+///
+/// The timeout of `budget` is measured from `start`.
+pub(crate) fn take_away_fields<R: Rng + ?Sized, F: Fn(&Board) -> bool>(
+    mut board: Board,
+    clues: usize,
+    budget: &Budget,
+    start: Instant,
+    rng: &mut R,
+    is_valid_puzzle: F,
+) -> Result<Board, BuildError> {
+    let width = board.get_width();
+    let height = board.get_height();
+    let mut fields = (0..width)
+        .flat_map(|x| (0..height).map(move |y| (x, y)))
+        .collect::<Vec<(usize, usize)>>();
+    shuffle(rng, &mut fields);
+
+    let mut num_clues = width * height;
+    for (x, y) in fields {
+        if num_clues <= clues {
+            break;
+        }
+        budget.check_timeout(start)?;
+        let field = board.get(x, y);
+        board.clear(x, y);
+        if is_valid_puzzle(&board) {
+            num_clues -= 1;
+        } else {
+            board.set(x, y, field);
+        }
+    }
+
+    Ok(board)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bruteforce::solver::solve;
+    use crate::field::Field;
+    use crate::rules::{is_board_full, is_board_valid};
+
+    fn count_clues(board: &Board) -> usize {
+        let height = board.get_height();
+        (0..board.get_width())
+            .flat_map(|x| (0..height).map(move |y| (x, y)))
+            .filter(|(x, y)| Field::Empty != board.get(*x, *y))
+            .count()
+    }
+
+    #[test]
+    fn full_board_is_valid() {
+        let board = create_full_board(8);
+
+        assert!(is_board_full(&board));
+        assert!(is_board_valid(&board));
+    }
+
+    #[test]
+    fn puzzle_board_is_solvable() {
+        let board = create_puzzle_board(8, 10);
+
+        assert!(solve(&board).is_some());
+    }
+
+    #[test]
+    fn unique_puzzle_board_has_unique_solution() {
+        let board = create_unique_puzzle_board(8, 0);
+
+        assert!(!is_board_full(&board));
+        assert!(has_unique_solution(&board));
+    }
+
+    #[test]
+    fn unique_puzzle_board_is_minimal() {
+        let mut board = create_unique_puzzle_board(6, 0);
+
+        for x in 0..6 {
+            for y in 0..6 {
+                let field = board.get(x, y);
+                if Field::Empty != field {
+                    board.clear(x, y);
+                    assert!(!has_unique_solution(&board));
+                    board.set(x, y, field);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_same_board() {
+        use crate::bruteforce::random::rng_from_seed;
+
+        assert_eq!(
+            create_puzzle_board_with_rng(8, 5, &mut rng_from_seed(7)),
+            create_puzzle_board_with_rng(8, 5, &mut rng_from_seed(7))
+        );
+        assert_eq!(
+            create_unique_puzzle_board_with_rng(6, 0, &mut rng_from_seed(7)),
+            create_unique_puzzle_board_with_rng(6, 0, &mut rng_from_seed(7))
+        );
+    }
+
+    #[test]
+    fn budget_for_huge_dimensions() {
+        assert_eq!(6_400, Budget::for_size(8).max_tries);
+        assert_eq!(
+            usize::MAX,
+            Budget::for_dimensions(usize::MAX / 2, 4).max_tries
+        );
+    }
+
+    #[test]
+    fn build_with_invalid_size() {
+        assert_eq!(
+            Err(BuildError::InvalidSize(0)),
+            Generator::square(0, BoardKind::Full).build()
+        );
+        assert_eq!(
+            Err(BuildError::InvalidSize(7)),
+            Generator::square(7, BoardKind::Puzzle { guesses: 10 }).build()
+        );
+        assert_eq!(
+            Err(BuildError::InvalidSize(3)),
+            Generator::square(3, BoardKind::Unique { clues: 0 }).build()
+        );
+    }
+
+    #[test]
+    fn build_with_exhausted_tries() {
+        let budget = Budget {
+            max_tries: 10,
+            timeout: None,
+        };
+
+        assert_eq!(
+            Err(BuildError::SearchExhausted { tries: 10 }),
+            Generator::square(8, BoardKind::Full)
+                .with_budget(budget)
+                .build()
+        );
+    }
+
+    #[test]
+    fn build_with_zero_timeout() {
+        let budget = Budget {
+            max_tries: usize::MAX,
+            timeout: Some(Duration::from_secs(0)),
+        };
+
+        for kind in &[
+            BoardKind::Full,
+            BoardKind::Puzzle { guesses: 10 },
+            BoardKind::Unique { clues: 0 },
+        ] {
+            assert_eq!(
+                Err(BuildError::Timeout(Duration::from_secs(0))),
+                Generator::square(8, *kind).with_budget(budget).build()
+            );
+        }
+    }
+
+    #[test]
+    fn build_with_enough_budget() {
+        let board = Generator::square(8, BoardKind::Full)
+            .with_budget(Budget::for_size(8))
+            .build()
+            .unwrap();
+
+        assert!(is_board_valid(&board));
+    }
+
+    #[test]
+    #[should_panic]
+    fn create_with_odd_size_panics() {
+        create_full_board(5);
+    }
+
+    #[test]
+    fn unique_puzzle_board_stops_at_clues() {
+        let board = create_unique_puzzle_board(8, 40);
+
+        assert_eq!(40, count_clues(&board));
+        assert!(has_unique_solution(&board));
+    }
+
+    #[test]
+    fn full_rect_board_is_valid() {
+        let board = Generator::new(6, 10, BoardKind::Full).build().unwrap();
+
+        assert_eq!(6, board.get_width());
+        assert_eq!(10, board.get_height());
+        assert!(is_board_full(&board));
+        assert!(is_board_valid(&board));
+    }
+
+    #[test]
+    fn rect_puzzle_board_is_solvable() {
+        let board = Generator::new(8, 6, BoardKind::Puzzle { guesses: 5 })
+            .build()
+            .unwrap();
+
+        assert_eq!(8, board.get_width());
+        assert_eq!(6, board.get_height());
+        assert!(solve(&board).is_some());
+    }
+
+    #[test]
+    fn unique_rect_puzzle_board_has_unique_solution() {
+        use crate::bruteforce::random::rng_from_seed;
+
+        let board = Generator::new(10, 6, BoardKind::Unique { clues: 0 })
+            .build_with_rng(&mut rng_from_seed(7))
+            .unwrap();
+
+        assert!(!is_board_full(&board));
+        assert!(has_unique_solution(&board));
+    }
+
+    #[test]
+    fn build_too_narrow_rect_board() {
+        let budget = Budget {
+            max_tries: 1000,
+            timeout: None,
+        };
+
+        assert_eq!(
+            Err(BuildError::SearchExhausted { tries: 1000 }),
+            Generator::new(8, 4, BoardKind::Full)
+                .with_budget(budget)
+                .build()
+        );
+    }
+
+    #[test]
+    fn build_rect_board_with_odd_height() {
+        assert_eq!(
+            Err(BuildError::InvalidSize(5)),
+            Generator::new(6, 5, BoardKind::Full).build()
+        );
+    }
 }
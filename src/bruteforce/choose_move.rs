@@ -5,14 +5,19 @@
 use crate::bruteforce::possible_move::PossibleMove;
 use crate::field::Field;
 
-use rand::seq::SliceRandom;
+use crate::bruteforce::random::{gen_bool, gen_index};
+
 use rand::{thread_rng, Rng};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Enum whether the selected move was taken because of
 /// it was the only possible move for the field or
 /// randomly chosen from multiple possible moves
 /// (with both X and O possible for the field).
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MoveSelection {
     /// was randomly selected from multiple options (either X or O)
     Random,
@@ -22,6 +27,7 @@ pub enum MoveSelection {
 
 /// Next move to perform.
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Move {
     /// Either `X` or `O`.
     pub field: Field,
@@ -45,6 +51,16 @@ pub struct Move {
 /// Only if there are no fixed options, it returns a randomly chosen move,
 /// which is marked as [`MoveSelection`](enum.MoveSelection.html)`::Random`.
 pub fn select_next_move(possible_moves: &[PossibleMove]) -> Option<Move> {
+    select_next_move_with_rng(possible_moves, &mut thread_rng())
+}
+
+/// Same as `fn` [`select_next_move`](fn.select_next_move.html), but takes the
+/// random choices from `rng`.
+#[allow(clippy::match_like_matches_macro)]
+pub fn select_next_move_with_rng<R: Rng + ?Sized>(
+    possible_moves: &[PossibleMove],
+    rng: &mut R,
+) -> Option<Move> {
     if possible_moves.is_empty() || possible_moves.contains(&PossibleMove::NoMove) {
         None
     } else {
@@ -55,11 +71,10 @@ pub fn select_next_move(possible_moves: &[PossibleMove]) -> Option<Move> {
                 _ => false,
             })
             .collect::<Vec<&PossibleMove>>();
-        let mut rng = thread_rng();
 
         if !single_options.is_empty() {
-            match single_options.choose(&mut rng) {
-                Some(&&PossibleMove::OneMove(x, y, field)) => {
+            match single_options[gen_index(rng, single_options.len())] {
+                &PossibleMove::OneMove(x, y, field) => {
                     let was_random = MoveSelection::Fixed;
                     Some(Move {
                         field,
@@ -71,9 +86,9 @@ pub fn select_next_move(possible_moves: &[PossibleMove]) -> Option<Move> {
                 _ => unreachable!(),
             }
         } else {
-            match possible_moves.choose(&mut rng) {
-                Some(&PossibleMove::TwoMoves(x, y)) => {
-                    let field = if rng.gen() { Field::X } else { Field::O };
+            match possible_moves[gen_index(rng, possible_moves.len())] {
+                PossibleMove::TwoMoves(x, y) => {
+                    let field = if gen_bool(rng) { Field::X } else { Field::O };
                     let was_random = MoveSelection::Random;
                     Some(Move {
                         field,
@@ -129,4 +144,37 @@ mod tests {
         let next_move = select_next_move(&possible_moves).unwrap();
         assert!(possible_moves.contains(&PossibleMove::TwoMoves(next_move.x, next_move.y,)));
     }
+
+    #[test]
+    fn select_same_move_for_same_seed() {
+        use crate::bruteforce::random::rng_from_seed;
+
+        let possible_moves = vec![
+            PossibleMove::TwoMoves(1, 1),
+            PossibleMove::TwoMoves(2, 1),
+            PossibleMove::TwoMoves(2, 2),
+        ];
+        for seed in 0..10 {
+            assert_eq!(
+                select_next_move_with_rng(&possible_moves, &mut rng_from_seed(seed)),
+                select_next_move_with_rng(&possible_moves, &mut rng_from_seed(seed))
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let m = Move {
+            field: Field::X,
+            x: 3,
+            y: 1,
+            was_random: MoveSelection::Random,
+        };
+
+        let json = serde_json::to_string(&m).unwrap();
+
+        assert_eq!(r#"{"field":"X","x":3,"y":1,"was_random":"Random"}"#, json);
+        assert_eq!(m, serde_json::from_str::<Move>(&json).unwrap());
+    }
 }
@@ -0,0 +1,401 @@
+//! This module implements a board, which keeps track of the numbers of
+//! X and O in every row and column and of all complete rows and columns:
+//! `struct` [`IncrementalBoard`](struct.IncrementalBoard.html).
+//!
+//! Checking a move on an `IncrementalBoard` needs neither to count the
+//! fields of its row and column nor to compare it with all other rows
+//! and columns. Hence, `set`, `clear` and `is_move_valid` take constant
+//! time (for boards of up to 64 fields per side).
+
+use crate::bruteforce::rules::is_valid_pair_rule;
+use crate::field::{Board, Field};
+
+use std::collections::HashMap;
+
+/// Numbers of X and O of a row or column.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+struct Counts {
+    x: usize,
+    o: usize,
+}
+
+impl Counts {
+    fn get(&self, field: Field) -> usize {
+        match field {
+            Field::X => self.x,
+            Field::O => self.o,
+            Field::Empty => 0,
+        }
+    }
+
+    fn add(&mut self, field: Field) {
+        match field {
+            Field::X => self.x += 1,
+            Field::O => self.o += 1,
+            Field::Empty => (),
+        }
+    }
+
+    fn remove(&mut self, field: Field) {
+        match field {
+            Field::X => self.x -= 1,
+            Field::O => self.o -= 1,
+            Field::Empty => (),
+        }
+    }
+
+    fn total(&self) -> usize {
+        self.x + self.o
+    }
+}
+
+/// Signatures (X masks) of complete rows or columns with the number of
+/// lines, which have the signature.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct CompleteLines(HashMap<Box<[u64]>, usize>);
+
+impl CompleteLines {
+    fn count(&self, signature: &[u64]) -> usize {
+        self.0.get(signature).copied().unwrap_or(0)
+    }
+
+    fn insert(&mut self, signature: &[u64]) {
+        match self.0.get_mut(signature) {
+            Some(count) => *count += 1,
+            None => {
+                self.0.insert(signature.into(), 1);
+            }
+        }
+    }
+
+    fn remove(&mut self, signature: &[u64]) {
+        let count = self.0.get_mut(signature).unwrap();
+        *count -= 1;
+        if 0 == *count {
+            self.0.remove(signature);
+        }
+    }
+}
+
+/// A board, which checks moves in constant time.
+///
+/// It keeps the numbers of X and O per row and column and
+/// the set of complete rows and columns up to date on every
+/// `set` and `clear`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IncrementalBoard {
+    board: Board,
+    row_counts: Vec<Counts>,
+    column_counts: Vec<Counts>,
+    complete_rows: CompleteLines,
+    complete_columns: CompleteLines,
+}
+
+impl IncrementalBoard {
+    /// Creates a new empty board with `width` columns and `height` rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` or `height` is odd or `0`.
+    pub fn new(width: usize, height: usize) -> IncrementalBoard {
+        IncrementalBoard {
+            board: Board::with_dimensions(width, height),
+            row_counts: vec![Counts::default(); height],
+            column_counts: vec![Counts::default(); width],
+            complete_rows: CompleteLines::default(),
+            complete_columns: CompleteLines::default(),
+        }
+    }
+
+    /// Creates a board with the fields of `board` (including its given fields).
+    pub fn from_board(board: &Board) -> IncrementalBoard {
+        let mut result = IncrementalBoard::new(board.get_width(), board.get_height());
+        for x in 0..board.get_width() {
+            for y in 0..board.get_height() {
+                let field = board.get(x, y);
+                if Field::Empty != field {
+                    result.set(x, y, field);
+                }
+            }
+        }
+        result.board = board.clone();
+        result
+    }
+
+    /// Returns the fields of the board.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Returns the fields of the board and drops the bookkeeping.
+    pub fn into_board(self) -> Board {
+        self.board
+    }
+
+    /// Returns field at column `x` and row `y`.
+    ///
+    /// # Panics
+    ///
+    /// See `fn` [`Board::get`](../../field/struct.Board.html#method.get).
+    pub fn get(&self, x: usize, y: usize) -> Field {
+        self.board.get(x, y)
+    }
+
+    /// Sets field at column `x` and row `y` to `field`.
+    ///
+    /// # Panics
+    ///
+    /// See `fn` [`Board::set`](../../field/struct.Board.html#method.set).
+    pub fn set(&mut self, x: usize, y: usize, field: Field) {
+        assert!(field != Field::Empty);
+        // check before the bookkeeping is touched
+        assert!(!self.board.is_given(x, y), "given fields cannot be set");
+
+        self.remove_complete_lines(x, y);
+        let old = self.board.get(x, y);
+        self.row_counts[y].remove(old);
+        self.column_counts[x].remove(old);
+        self.board.set(x, y, field);
+        self.row_counts[y].add(field);
+        self.column_counts[x].add(field);
+        self.insert_complete_lines(x, y);
+    }
+
+    /// Sets field at column `x` and row `y` to `Empty`.
+    ///
+    /// # Panics
+    ///
+    /// See `fn` [`Board::clear`](../../field/struct.Board.html#method.clear).
+    pub fn clear(&mut self, x: usize, y: usize) {
+        let old = self.board.get(x, y);
+        assert!(Field::Empty != old);
+        assert!(!self.board.is_given(x, y), "given fields cannot be cleared");
+
+        self.remove_complete_lines(x, y);
+        self.row_counts[y].remove(old);
+        self.column_counts[x].remove(old);
+        self.board.clear(x, y);
+    }
+
+    /// Returns whether the board has no `Empty` fields.
+    pub fn is_full(&self) -> bool {
+        let width = self.board.get_width();
+        self.row_counts.iter().all(|counts| width == counts.total())
+    }
+
+    /// Same as `fn` [`is_move_valid`](../rules/fn.is_move_valid.html),
+    /// but does not walk through the row and column of the field.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field at column `x` and row `y` is `Empty`.
+    pub fn is_move_valid(&self, x: usize, y: usize) -> bool {
+        let field = self.board.get(x, y);
+        let width = self.board.get_width();
+        let height = self.board.get_height();
+
+        is_valid_pair_rule(&self.board, x, y)
+            && self.row_counts[y].get(field) <= width / 2
+            && self.column_counts[x].get(field) <= height / 2
+            && (!self.is_row_complete(y)
+                || 1 == self.complete_rows.count(self.board.row_masks(y).0))
+            && (!self.is_column_complete(x)
+                || 1 == self.complete_columns.count(self.board.column_masks(x).0))
+    }
+
+    fn is_row_complete(&self, y: usize) -> bool {
+        self.board.get_width() == self.row_counts[y].total()
+    }
+
+    fn is_column_complete(&self, x: usize) -> bool {
+        self.board.get_height() == self.column_counts[x].total()
+    }
+
+    /// Removes row `y` and column `x` from the complete lines
+    /// before the field at column `x` and row `y` changes.
+    fn remove_complete_lines(&mut self, x: usize, y: usize) {
+        if self.is_row_complete(y) {
+            self.complete_rows.remove(self.board.row_masks(y).0);
+        }
+        if self.is_column_complete(x) {
+            self.complete_columns.remove(self.board.column_masks(x).0);
+        }
+    }
+
+    /// Adds row `y` and column `x` to the complete lines
+    /// after the field at column `x` and row `y` changed.
+    fn insert_complete_lines(&mut self, x: usize, y: usize) {
+        if self.is_row_complete(y) {
+            self.complete_rows.insert(self.board.row_masks(y).0);
+        }
+        if self.is_column_complete(x) {
+            self.complete_columns.insert(self.board.column_masks(x).0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bruteforce::random::{gen_bool, gen_index, rng_from_seed};
+    use crate::rules::{is_board_full, is_move_valid};
+    use std::panic::{self, AssertUnwindSafe};
+    use std::str::FromStr;
+
+    #[test]
+    fn counts_fields() {
+        let mut board = IncrementalBoard::new(4, 4);
+
+        board.set(0, 0, Field::X);
+        board.set(1, 0, Field::X);
+        board.set(1, 2, Field::O);
+
+        assert_eq!(Counts { x: 2, o: 0 }, board.row_counts[0]);
+        assert_eq!(Counts { x: 1, o: 1 }, board.column_counts[1]);
+
+        board.set(1, 0, Field::O);
+        board.clear(0, 0);
+
+        assert_eq!(Counts { x: 0, o: 1 }, board.row_counts[0]);
+        assert_eq!(Counts { x: 0, o: 2 }, board.column_counts[1]);
+        assert_eq!(Counts::default(), board.column_counts[0]);
+    }
+
+    #[test]
+    fn finds_duplicate_rows() {
+        let mut board = IncrementalBoard::from_board(
+            &Board::from_str(
+                "
+                X O X O
+                _ _ _ _
+                X O X _
+                _ _ _ _",
+            )
+            .unwrap(),
+        );
+        assert!(board.is_move_valid(2, 2));
+
+        board.set(3, 2, Field::O);
+
+        assert!(!board.is_move_valid(3, 2));
+        assert!(!board.is_move_valid(0, 0));
+
+        board.clear(3, 2);
+
+        assert!(board.is_move_valid(0, 0));
+        assert_eq!(1, board.complete_rows.0.len());
+    }
+
+    #[test]
+    fn finds_unbalanced_lines_and_triples() {
+        let board = IncrementalBoard::from_board(
+            &Board::from_str(
+                "
+                X O X X
+                _ _ _ O
+                _ _ _ O
+                _ _ _ O",
+            )
+            .unwrap(),
+        );
+
+        assert!(!board.is_move_valid(0, 0));
+        assert!(!board.is_move_valid(3, 2));
+        assert!(board.is_move_valid(1, 0));
+    }
+
+    #[test]
+    fn is_full() {
+        let mut board = IncrementalBoard::new(2, 2);
+        board.set(0, 0, Field::X);
+        board.set(1, 0, Field::O);
+        board.set(0, 1, Field::O);
+        assert!(!board.is_full());
+
+        board.set(1, 1, Field::X);
+
+        assert!(board.is_full());
+        assert!(is_board_full(board.board()));
+    }
+
+    #[test]
+    fn agrees_with_rules() {
+        let mut rng = rng_from_seed(11);
+        let size = 6;
+        let mut board = IncrementalBoard::new(size, size);
+
+        for _ in 0..2000 {
+            let x = gen_index(&mut rng, size);
+            let y = gen_index(&mut rng, size);
+            if Field::Empty != board.get(x, y) && gen_bool(&mut rng) {
+                board.clear(x, y);
+            } else {
+                board.set(
+                    x,
+                    y,
+                    if gen_bool(&mut rng) {
+                        Field::X
+                    } else {
+                        Field::O
+                    },
+                );
+            }
+
+            assert_eq!(IncrementalBoard::from_board(board.board()), board);
+            for x in 0..size {
+                for y in 0..size {
+                    if Field::Empty != board.get(x, y) {
+                        assert_eq!(
+                            is_move_valid(board.board(), x, y),
+                            board.is_move_valid(x, y)
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn from_board_keeps_given_fields() {
+        let mut given = Board::new(2);
+        given.set_given(0, 0, Field::X);
+
+        let board = IncrementalBoard::from_board(&given);
+
+        assert!(board.board().is_given(0, 0));
+        assert_eq!(Counts { x: 1, o: 0 }, board.row_counts[0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "given fields cannot be set")]
+    fn set_given_field() {
+        let mut given = Board::new(2);
+        given.set_given(0, 0, Field::X);
+        let mut board = IncrementalBoard::from_board(&given);
+
+        board.set(0, 0, Field::O);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_out_of_bounds() {
+        let mut board = IncrementalBoard::new(2, 2);
+
+        board.set(0, 2, Field::O);
+    }
+
+    #[test]
+    fn failed_set_and_clear_keep_bookkeeping() {
+        let given = Board::from_str("X* O\nO X").unwrap();
+        let mut board = IncrementalBoard::from_board(&given);
+        let expected = board.clone();
+
+        let set = panic::catch_unwind(AssertUnwindSafe(|| board.set(0, 0, Field::O)));
+        let clear = panic::catch_unwind(AssertUnwindSafe(|| board.clear(0, 0)));
+        let outside = panic::catch_unwind(AssertUnwindSafe(|| board.set(2, 0, Field::O)));
+
+        assert!(set.is_err() && clear.is_err() && outside.is_err());
+        assert_eq!(expected, board);
+        assert_eq!(IncrementalBoard::from_board(board.board()), board);
+    }
+}
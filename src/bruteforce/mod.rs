@@ -4,8 +4,19 @@
 
 pub mod build_board;
 pub mod choose_move;
+pub mod incremental;
 pub mod possible_move;
+pub mod random;
 pub mod rules;
+pub mod solver;
 
 pub use self::build_board::create_full_board;
+pub use self::build_board::create_full_board_with_rng;
 pub use self::build_board::create_puzzle_board;
+pub use self::build_board::create_puzzle_board_with_rng;
+pub use self::build_board::create_unique_puzzle_board;
+pub use self::build_board::create_unique_puzzle_board_with_rng;
+pub use self::build_board::{BoardKind, Budget, BuildError, Generator};
+pub use self::incremental::IncrementalBoard;
+pub use self::random::{rng_from_seed, SeededRng};
+pub use self::solver::{count_solutions, has_unique_solution, solve};
@@ -1,6 +1,10 @@
 //! This module contains the code to determine the options for any
 //! empty field of a board: `fn` [`calc_possible_moves`](fn.calc_possible_moves).
+//! `fn` [`calc_incremental_possible_moves`](fn.calc_incremental_possible_moves.html)
+//! does the same faster on an
+//! [`IncrementalBoard`](../incremental/struct.IncrementalBoard.html).
 
+use crate::bruteforce::incremental::IncrementalBoard;
 use crate::bruteforce::rules::is_move_valid;
 use crate::field::{Board, Field};
 
@@ -25,15 +29,36 @@ fn calc_possible_move(board: &mut Board, x: usize, y: usize) -> PossibleMove {
         board.set(x, y, Field::O);
         let y_possible = is_move_valid(board, x, y);
         board.clear(x, y);
-        if x_possible && y_possible {
-            PossibleMove::TwoMoves(x, y)
-        } else if x_possible {
-            PossibleMove::OneMove(x, y, Field::X)
-        } else if y_possible {
-            PossibleMove::OneMove(x, y, Field::O)
-        } else {
-            PossibleMove::NoMove
-        }
+        to_possible_move(x, y, x_possible, y_possible)
+    } else {
+        PossibleMove::NoMove
+    }
+}
+
+fn calc_incremental_possible_move(
+    board: &mut IncrementalBoard,
+    x: usize,
+    y: usize,
+) -> PossibleMove {
+    if Field::Empty == board.get(x, y) {
+        board.set(x, y, Field::X);
+        let x_possible = board.is_move_valid(x, y);
+        board.set(x, y, Field::O);
+        let y_possible = board.is_move_valid(x, y);
+        board.clear(x, y);
+        to_possible_move(x, y, x_possible, y_possible)
+    } else {
+        PossibleMove::NoMove
+    }
+}
+
+fn to_possible_move(x: usize, y: usize, x_possible: bool, y_possible: bool) -> PossibleMove {
+    if x_possible && y_possible {
+        PossibleMove::TwoMoves(x, y)
+    } else if x_possible {
+        PossibleMove::OneMove(x, y, Field::X)
+    } else if y_possible {
+        PossibleMove::OneMove(x, y, Field::O)
     } else {
         PossibleMove::NoMove
     }
@@ -42,8 +67,8 @@ fn calc_possible_move(board: &mut Board, x: usize, y: usize) -> PossibleMove {
 /// For all empty fields on board `board` returns the possible moves.
 pub fn calc_possible_moves(board: &mut Board) -> Vec<PossibleMove> {
     let mut result = Vec::new();
-    for x in 0..board.get_size() {
-        for y in 0..board.get_size() {
+    for x in 0..board.get_width() {
+        for y in 0..board.get_height() {
             if Field::Empty == board.get(x, y) {
                 result.push(calc_possible_move(board, x, y))
             }
@@ -52,6 +77,20 @@ pub fn calc_possible_moves(board: &mut Board) -> Vec<PossibleMove> {
     result
 }
 
+/// Same as `fn` [`calc_possible_moves`](fn.calc_possible_moves.html), but
+/// checks every move in constant time on the incremental board `board`.
+pub fn calc_incremental_possible_moves(board: &mut IncrementalBoard) -> Vec<PossibleMove> {
+    let mut result = Vec::new();
+    for x in 0..board.board().get_width() {
+        for y in 0..board.board().get_height() {
+            if Field::Empty == board.get(x, y) {
+                result.push(calc_incremental_possible_move(board, x, y))
+            }
+        }
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -193,4 +232,25 @@ mod tests {
             calc_possible_move(&mut board, 0, 0)
         );
     }
+
+    #[test]
+    fn incremental_possible_moves_are_equal() {
+        let board = Board::from_str(
+            "
+            X X _ _ _ _
+            O _ _ X _ _
+            _ _ O _ _ _
+            _ O _ _ _ X
+            _ _ _ _ O _
+            _ X _ _ _ _",
+        )
+        .unwrap();
+        let mut incremental = IncrementalBoard::from_board(&board);
+
+        assert_eq!(
+            calc_possible_moves(&mut board.clone()),
+            calc_incremental_possible_moves(&mut incremental)
+        );
+        assert_eq!(&board, incremental.board());
+    }
 }
@@ -0,0 +1,88 @@
+//! This module provides the random numbers for the puzzle generator.
+//!
+//! All functions of the generator are available with a caller supplied
+//! random number generator (`*_with_rng`).
+//! Use `fn` [`rng_from_seed`](fn.rng_from_seed.html) to get a
+//! random number generator, which creates the same boards for the same seed
+//! on every platform.
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Random number generator returned by `fn` [`rng_from_seed`](fn.rng_from_seed.html).
+pub type SeededRng = ChaCha8Rng;
+
+/// Returns a random number generator, whose random numbers only depend
+/// on `seed`.
+///
+/// ```
+/// use binoxxo::bruteforce::{create_full_board_with_rng, rng_from_seed};
+///
+/// let board = create_full_board_with_rng(10, &mut rng_from_seed(4711));
+/// assert_eq!(board, create_full_board_with_rng(10, &mut rng_from_seed(4711)));
+/// ```
+pub fn rng_from_seed(seed: u64) -> SeededRng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+// The generator only uses the raw `u64` output of the random number
+// generator (instead of `gen_range`, `choose`, etc.), so that the created
+// boards do neither depend on the width of `usize` nor on the sampling
+// algorithms of the `rand` crate.
+
+/// Returns a random index in `0..len`.
+///
+/// # Panics
+///
+/// Panics if `len` is `0`.
+pub(crate) fn gen_index<R: Rng + ?Sized>(rng: &mut R, len: usize) -> usize {
+    assert!(0 < len);
+
+    (rng.next_u64() % (len as u64)) as usize
+}
+
+/// Returns a random `bool`.
+pub(crate) fn gen_bool<R: Rng + ?Sized>(rng: &mut R) -> bool {
+    0 == rng.next_u64() % 2
+}
+
+/// Shuffles `values` in place (Fisher-Yates).
+pub(crate) fn shuffle<T, R: Rng + ?Sized>(rng: &mut R, values: &mut [T]) {
+    for i in (1..values.len()).rev() {
+        values.swap(i, gen_index(rng, i + 1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let mut rng1 = rng_from_seed(42);
+        let mut rng2 = rng_from_seed(42);
+
+        for len in 1..100 {
+            assert_eq!(gen_index(&mut rng1, len), gen_index(&mut rng2, len));
+        }
+    }
+
+    #[test]
+    fn gen_index_in_range() {
+        let mut rng = rng_from_seed(42);
+
+        for len in 1..100 {
+            assert!(gen_index(&mut rng, len) < len);
+        }
+    }
+
+    #[test]
+    fn shuffle_keeps_values() {
+        let mut values = (0..20).collect::<Vec<usize>>();
+
+        shuffle(&mut rng_from_seed(42), &mut values);
+        values.sort_unstable();
+
+        assert_eq!((0..20).collect::<Vec<usize>>(), values);
+    }
+}
@@ -1,112 +1,167 @@
 //! This module can check entiere boards or single moves, whether the fullfill
 //! the binoxxo rules.
+//! `fn` [`check_board`](fn.check_board.html) also reports which rules
+//! are broken and where.
 
-use crate::field::{Board, Field};
+use crate::field::{Board, Field, MASK_BITS};
 
 use std::collections::HashSet;
+use std::fmt;
 
-fn is_valid_pair_rule(board: &Board, x: usize, y: usize) -> bool {
-    let current = board.get(x, y);
-    let size = board.get_size();
-    assert!(Field::Empty != current);
-
-    let three_up = y > 1 && current == board.get(x, y - 2) && current == board.get(x, y - 1);
-    let three_down =
-        y < size - 2 && current == board.get(x, y + 1) && current == board.get(x, y + 2);
-    let three_middle =
-        y > 0 && y < size - 1 && current == board.get(x, y - 1) && current == board.get(x, y + 1);
+/// Returns whether bit `i` of the bit mask `words` is set.
+fn is_bit_set(words: &[u64], i: usize) -> bool {
+    0 != words[i / MASK_BITS] & (1 << (i % MASK_BITS))
+}
 
-    let three_left = x > 1 && current == board.get(x - 2, y) && current == board.get(x - 1, y);
-    let three_right =
-        x < size - 2 && current == board.get(x + 1, y) && current == board.get(x + 2, y);
-    let three_center =
-        x > 0 && x < size - 1 && current == board.get(x - 1, y) && current == board.get(x + 1, y);
+/// Returns the number of set bits of the bit mask `words`.
+fn count_bits(words: &[u64]) -> usize {
+    words.iter().map(|word| word.count_ones() as usize).sum()
+}
 
-    !three_up && !three_down && !three_middle && !three_left && !three_right && !three_center
+/// Returns whether the set bit `i` of the bit mask `words` is part of
+/// three adjacent set bits.
+fn has_triple_with(words: &[u64], i: usize) -> bool {
+    // bits i - 2 to i + 2 (bits outside of the line are not set)
+    let window = (0..5)
+        .filter(|j| i + j >= 2 && i + j - 2 < words.len() * MASK_BITS)
+        .filter(|j| is_bit_set(words, i + j - 2))
+        .fold(0u64, |window, j| window | (1 << j));
+    0 != window & (window >> 1) & (window >> 2)
 }
 
-fn is_valid_colum(board: &Board, x: usize, y: usize) -> bool {
-    let current = board.get(x, y);
-    let mut count = 0;
+/// Returns whether the bit mask `words` has three adjacent set bits.
+fn has_triple(words: &[u64]) -> bool {
+    words.iter().enumerate().any(|(i, word)| {
+        // carry in the lowest bits of the next word
+        let next = words.get(i + 1).copied().unwrap_or(0);
+        let shifted_1 = (word >> 1) | (next << (MASK_BITS - 1));
+        let shifted_2 = (word >> 2) | (next << (MASK_BITS - 2));
+        0 != word & shifted_1 & shifted_2
+    })
+}
 
-    for y in 0..board.get_size() {
-        if current == board.get(x, y) {
-            count += 1;
-        }
+/// Returns the bit mask of `field` out of the X and O masks `masks`.
+fn select_mask<'a>((x, o): (&'a [u64], &'a [u64]), field: Field) -> &'a [u64] {
+    match field {
+        Field::X => x,
+        Field::O => o,
+        Field::Empty => panic!("there is no bit mask of empty fields"),
     }
+}
+
+pub(crate) fn is_valid_pair_rule(board: &Board, x: usize, y: usize) -> bool {
+    let current = board.get(x, y);
+    assert!(Field::Empty != current);
 
-    count <= board.get_size() / 2
+    !has_triple_with(select_mask(board.column_masks(x), current), y)
+        && !has_triple_with(select_mask(board.row_masks(y), current), x)
 }
 
-fn is_valid_row(board: &Board, x: usize, y: usize) -> bool {
+pub(crate) fn is_valid_colum(board: &Board, x: usize, y: usize) -> bool {
     let current = board.get(x, y);
-    let mut count = 0;
 
-    for x in 0..board.get_size() {
-        if current == board.get(x, y) {
-            count += 1;
-        }
-    }
+    count_bits(select_mask(board.column_masks(x), current)) <= board.get_height() / 2
+}
 
-    count <= board.get_size() / 2
+pub(crate) fn is_valid_row(board: &Board, x: usize, y: usize) -> bool {
+    let current = board.get(x, y);
+
+    count_bits(select_mask(board.row_masks(y), current)) <= board.get_width() / 2
 }
 
-fn calc_column_siganture(board: &Board, x: usize) -> Option<i64> {
-    let mut sig = 0;
-    let mut power_of_2 = 1;
-    for y in 0..board.get_size() {
-        match board.get(x, y) {
-            Field::X => sig += power_of_2,
-            Field::O => (),
-            Field::Empty => return None,
-        }
-        power_of_2 *= 2;
+/// Returns the signature of a line with `len` fields and the bit masks
+/// `(x, o)`: the X mask if the line is complete, otherwise `None`.
+///
+/// Two complete lines are equal if and only if their signatures are equal.
+fn calc_line_siganture<'a>((x, o): (&'a [u64], &'a [u64]), len: usize) -> Option<&'a [u64]> {
+    if len == count_bits(x) + count_bits(o) {
+        Some(x)
+    } else {
+        None
     }
-    Some(sig)
 }
 
-fn is_unique_column(board: &Board, x: usize) -> bool {
+fn calc_column_siganture(board: &Board, x: usize) -> Option<&[u64]> {
+    calc_line_siganture(board.column_masks(x), board.get_height())
+}
+
+pub(crate) fn is_unique_column(board: &Board, x: usize) -> bool {
+    // a complete column is only equal to columns with the same X and O masks
     match calc_column_siganture(board, x) {
-        Some(reference) => {
-            (0..board.get_size())
+        Some(_) => {
+            let reference = board.column_masks(x);
+            (0..board.get_width())
                 .filter(|col| *col != x)
-                .map(|col| calc_column_siganture(board, col))
-                .filter(|sig| *sig == Some(reference))
-                .count()
-                == 0
+                .all(|col| board.column_masks(col) != reference)
         }
         None => true,
     }
 }
 
-fn calc_row_siganture(board: &Board, y: usize) -> Option<i64> {
-    let mut sig = 0;
-    let mut power_of_2 = 1;
-    for x in 0..board.get_size() {
-        match board.get(x, y) {
-            Field::X => sig += power_of_2,
-            Field::O => (),
-            Field::Empty => return None,
-        }
-        power_of_2 *= 2;
-    }
-    Some(sig)
+fn calc_row_siganture(board: &Board, y: usize) -> Option<&[u64]> {
+    calc_line_siganture(board.row_masks(y), board.get_width())
 }
 
-fn is_unique_row(board: &Board, y: usize) -> bool {
+pub(crate) fn is_unique_row(board: &Board, y: usize) -> bool {
+    // a complete row is only equal to rows with the same X and O masks
     match calc_row_siganture(board, y) {
-        Some(reference) => {
-            (0..board.get_size())
+        Some(_) => {
+            let reference = board.row_masks(y);
+            (0..board.get_height())
                 .filter(|row| *row != y)
-                .map(|row| calc_row_siganture(board, row))
-                .filter(|sig| *sig == Some(reference))
-                .count()
-                == 0
+                .all(|row| board.row_masks(row) != reference)
         }
         None => true,
     }
 }
 
+/// Returns the number of ways to fill the empty fields of a single
+/// row or column `line` without breaking the pair rule or the balance
+/// of X and O (saturates at `u64::MAX`).
+/// The uniqueness of rows and columns is not checked.
+pub(crate) fn count_line_completions(line: &[Field]) -> u64 {
+    let half = line.len() / 2;
+    // states: (number of X, last field, length of run of last field) -> count
+    let mut states = vec![((0usize, Field::Empty, 0usize), 1u64)];
+    for (index, field) in line.iter().enumerate() {
+        let mut next_states: Vec<((usize, Field, usize), u64)> = Vec::new();
+        for &((num_x, last, run), count) in &states {
+            for candidate in &[Field::X, Field::O] {
+                if Field::Empty != *field && field != candidate {
+                    continue;
+                }
+                let next_num_x = if Field::X == *candidate {
+                    num_x + 1
+                } else {
+                    num_x
+                };
+                let next_run = if last == *candidate { run + 1 } else { 1 };
+                if next_num_x > half || index + 1 - next_num_x > half || next_run > 2 {
+                    continue;
+                }
+                let next = (next_num_x, *candidate, next_run);
+                match next_states.iter_mut().find(|(state, _)| *state == next) {
+                    Some((_, next_count)) => *next_count = next_count.saturating_add(count),
+                    None => next_states.push((next, count)),
+                }
+            }
+        }
+        if next_states.is_empty() {
+            return 0;
+        }
+        states = next_states;
+    }
+    states
+        .iter()
+        .fold(0u64, |sum, (_, count)| sum.saturating_add(*count))
+}
+
+/// Returns whether the empty fields of a single row or column `line` can be
+/// filled without breaking the pair rule or the balance of X and O.
+pub(crate) fn can_complete_line(line: &[Field]) -> bool {
+    0 < count_line_completions(line)
+}
+
 /// Returns whether the content of the field at column `x` and row `y`
 /// on board `board` fullfills the binoxxo rules.
 /// The rest of the board is *not* checked, and, hence, even if
@@ -122,122 +177,230 @@ pub fn is_move_valid(board: &Board, x: usize, y: usize) -> bool {
 }
 
 fn are_columns_unique(board: &Board) -> bool {
-    let column_sigs = (0..board.get_size())
+    let column_sigs = (0..board.get_width())
         .map(|col| calc_column_siganture(board, col))
-        .collect::<HashSet<Option<i64>>>();
-    column_sigs.len() == board.get_size()
+        .collect::<HashSet<Option<&[u64]>>>();
+    column_sigs.len() == board.get_width()
 }
 
 fn are_rows_unique(board: &Board) -> bool {
-    let row_sigs = (0..board.get_size())
+    let row_sigs = (0..board.get_height())
         .map(|row| calc_row_siganture(board, row))
-        .collect::<HashSet<Option<i64>>>();
-    row_sigs.len() == board.get_size()
+        .collect::<HashSet<Option<&[u64]>>>();
+    row_sigs.len() == board.get_height()
+}
+
+fn is_line_balanced((x, o): (&[u64], &[u64]), len: usize) -> bool {
+    (len / 2, len / 2) == (count_bits(x), count_bits(o))
 }
 
 fn are_rows_balanced(board: &Board) -> bool {
-    let size = board.get_size();
-    let half_size = size / 2;
-    let get_num_fields =
-        |field: Field, y: usize| (0..size).filter(|x| field == board.get(*x, y)).count();
-    (0..size)
-        .map(|y| (get_num_fields(Field::X, y), get_num_fields(Field::O, y)))
-        .all(|num_xo| (half_size, half_size) == num_xo)
+    (0..board.get_height()).all(|y| is_line_balanced(board.row_masks(y), board.get_width()))
 }
 
 fn are_columns_balanced(board: &Board) -> bool {
-    let size = board.get_size();
-    let half_size = size / 2;
-    let get_num_fields =
-        |field: Field, x: usize| (0..size).filter(|y| field == board.get(x, *y)).count();
-    (0..size)
-        .map(|x| (get_num_fields(Field::X, x), get_num_fields(Field::O, x)))
-        .all(|num_xo| (half_size, half_size) == num_xo)
+    (0..board.get_width()).all(|x| is_line_balanced(board.column_masks(x), board.get_height()))
 }
 
-/// Checks that field at position (x, y) on board has not the same two neightbors (vertically).
-/// Ignores empty.
-#[inline]
-fn has_no_more_than_two_same_neightbors_vert(board: &Board, x: usize, y: usize) -> bool {
-    let f = board.get(x, y);
+fn has_no_more_than_two_same_neightbors(board: &Board) -> bool {
+    let has_no_triple = |(x, o): (&[u64], &[u64])| !has_triple(x) && !has_triple(o);
+    (0..board.get_height()).all(|y| has_no_triple(board.row_masks(y)))
+        && (0..board.get_width()).all(|x| has_no_triple(board.column_masks(x)))
+}
 
-    Field::Empty == f || !((f == board.get(x, y - 1)) && (f == board.get(x, y + 1)))
+/// Returns whether all fields on the board `board` fullfill
+/// the binoxxo rules.
+///
+/// To check a single move use: `fn` [`is_move_valid`](fn.is_move_valid.html).
+pub fn is_board_valid(board: &Board) -> bool {
+    are_columns_unique(board)
+        && are_rows_unique(board)
+        && are_rows_balanced(board)
+        && are_columns_balanced(board)
+        && has_no_more_than_two_same_neightbors(board)
 }
 
-/// Checks that field at position (x, y) on board has not the same two neightbors (horizontally).
-/// Ignores empty.
-#[inline]
-fn has_no_more_than_two_same_neightbors_horz(board: &Board, x: usize, y: usize) -> bool {
-    let f = board.get(x, y);
+/// Returns whether the board is full (i.e. has no `Empty` fields).
+pub fn is_board_full(board: &Board) -> bool {
+    (0..board.get_height()).all(|y| calc_row_siganture(board, y).is_some())
+}
 
-    Field::Empty == f || !((f == board.get(x - 1, y)) && (f == board.get(x + 1, y)))
+/// A broken binoxxo rule found by `fn` [`check_board`](fn.check_board.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Violation {
+    /// three equal adjacent fields (column, row) in a row or column
+    Triple([(usize, usize); 3]),
+    /// row `y` has more than half fields of token `field`
+    UnbalancedRow {
+        /// row
+        y: usize,
+        /// token with too many fields (either `X` or `O`)
+        field: Field,
+    },
+    /// column `x` has more than half fields of token `field`
+    UnbalancedColumn {
+        /// column
+        x: usize,
+        /// token with too many fields (either `X` or `O`)
+        field: Field,
+    },
+    /// two complete rows are equal
+    DuplicateRows(usize, usize),
+    /// two complete columns are equal
+    DuplicateColumns(usize, usize),
 }
 
-fn has_no_more_than_two_same_neightbors(board: &Board) -> bool {
-    let size = board.get_size();
-    // first check the following area of board (exemplary 4 x 4 board)
-    // _ v v _
-    // h x x h
-    // h x x h
-    // _ v v _
-    //
-    // key:
-    // - _ not checked
-    // - v only checked in vertically direction
-    // - h only checked in horizontally direction
-    // - x checked in vertically and horizontally direction
-    for x in 1..(size - 1) {
-        for y in 1..(size - 1) {
-            if !has_no_more_than_two_same_neightbors_horz(board, x, y)
-                || !has_no_more_than_two_same_neightbors_vert(board, x, y)
-            {
-                return false;
+impl Violation {
+    /// Returns all fields (column, row) involved in the violation
+    /// on board `board`.
+    pub fn positions(&self, board: &Board) -> Vec<(usize, usize)> {
+        let width = board.get_width();
+        let height = board.get_height();
+        match *self {
+            Violation::Triple(fields) => fields.to_vec(),
+            Violation::UnbalancedRow { y, .. } => (0..width).map(|x| (x, y)).collect(),
+            Violation::UnbalancedColumn { x, .. } => (0..height).map(|y| (x, y)).collect(),
+            Violation::DuplicateRows(y1, y2) => (0..width)
+                .map(|x| (x, y1))
+                .chain((0..width).map(|x| (x, y2)))
+                .collect(),
+            Violation::DuplicateColumns(x1, x2) => (0..height)
+                .map(|y| (x1, y))
+                .chain((0..height).map(|y| (x2, y)))
+                .collect(),
+        }
+    }
+}
+
+/// Human-readable description (rows and columns are counted from 1).
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let other = |field: Field| {
+            if Field::X == field {
+                Field::O
+            } else {
+                Field::X
+            }
+        };
+        match *self {
+            Violation::Triple([(x1, y1), _, (x3, y3)]) => write!(
+                f,
+                "Three equal fields in a line from column {}, row {} to column {}, row {}.",
+                x1 + 1,
+                y1 + 1,
+                x3 + 1,
+                y3 + 1
+            ),
+            Violation::UnbalancedRow { y, field } => {
+                write!(f, "Row {} has more {} than {}.", y + 1, field, other(field))
+            }
+            Violation::UnbalancedColumn { x, field } => {
+                write!(
+                    f,
+                    "Column {} has more {} than {}.",
+                    x + 1,
+                    field,
+                    other(field)
+                )
+            }
+            Violation::DuplicateRows(y1, y2) => {
+                write!(f, "Row {} is equal to row {}.", y1 + 1, y2 + 1)
+            }
+            Violation::DuplicateColumns(x1, x2) => {
+                write!(f, "Column {} is equal to column {}.", x1 + 1, x2 + 1)
             }
         }
     }
-    // the next two loops check the fields missed by the check above
-    // check top and bottom horizontally
-    for x in 1..(size - 1) {
-        if !has_no_more_than_two_same_neightbors_horz(board, x, 0)
-            || !has_no_more_than_two_same_neightbors_horz(board, x, size - 1)
+}
+
+fn find_triples(board: &Board, violations: &mut Vec<Violation>) {
+    let width = board.get_width();
+    let height = board.get_height();
+    let mut check = |fields: [(usize, usize); 3]| {
+        let current = board.get(fields[0].0, fields[0].1);
+        if Field::Empty != current
+            && fields[1..]
+                .iter()
+                .all(|(x, y)| current == board.get(*x, *y))
         {
-            return false;
+            violations.push(Violation::Triple(fields));
+        }
+    };
+    for y in 0..height {
+        for x in 0..(width - 2) {
+            check([(x, y), (x + 1, y), (x + 2, y)]);
         }
     }
-    // check left and right vertically
-    for y in 1..(size - 1) {
-        if !has_no_more_than_two_same_neightbors_vert(board, 0, y)
-            || !has_no_more_than_two_same_neightbors_vert(board, size - 1, y)
-        {
-            return false;
+    for x in 0..width {
+        for y in 0..(height - 2) {
+            check([(x, y), (x, y + 1), (x, y + 2)]);
         }
     }
-    true
 }
 
-/// Returns whether all fields on the board `board` fullfill
-/// the binoxxo rules.
-///
-/// To check a single move use: `fn` [`is_move_valid`](fn.is_move_valid.html).
-pub fn is_board_valid(board: &Board) -> bool {
-    are_columns_unique(board)
-        && are_rows_unique(board)
-        && are_rows_balanced(board)
-        && are_columns_balanced(board)
-        && has_no_more_than_two_same_neightbors(board)
+fn find_unbalanced_lines(board: &Board, violations: &mut Vec<Violation>) {
+    let width = board.get_width();
+    let height = board.get_height();
+    for field in &[Field::X, Field::O] {
+        let field = *field;
+        for y in 0..height {
+            if count_bits(select_mask(board.row_masks(y), field)) > width / 2 {
+                violations.push(Violation::UnbalancedRow { y, field });
+            }
+        }
+    }
+    for field in &[Field::X, Field::O] {
+        let field = *field;
+        for x in 0..width {
+            if count_bits(select_mask(board.column_masks(x), field)) > height / 2 {
+                violations.push(Violation::UnbalancedColumn { x, field });
+            }
+        }
+    }
 }
 
-/// Returns whether the board is full (i.e. has no `Empty` fields).
-pub fn is_board_full(board: &Board) -> bool {
-    let size = board.get_size();
-    for x in 0..size {
-        for y in 0..size {
-            if Field::Empty == board.get(x, y) {
-                return false;
+fn find_duplicate_lines(board: &Board, violations: &mut Vec<Violation>) {
+    let width = board.get_width();
+    let height = board.get_height();
+    let row_sigs = (0..height)
+        .map(|y| calc_row_siganture(board, y))
+        .collect::<Vec<Option<&[u64]>>>();
+    let column_sigs = (0..width)
+        .map(|x| calc_column_siganture(board, x))
+        .collect::<Vec<Option<&[u64]>>>();
+    for i in 0..height {
+        for j in (i + 1)..height {
+            if row_sigs[i].is_some() && row_sigs[i] == row_sigs[j] {
+                violations.push(Violation::DuplicateRows(i, j));
+            }
+        }
+    }
+    for i in 0..width {
+        for j in (i + 1)..width {
+            if column_sigs[i].is_some() && column_sigs[i] == column_sigs[j] {
+                violations.push(Violation::DuplicateColumns(i, j));
             }
         }
     }
-    true
+}
+
+/// Returns all broken binoxxo rules of the (partially filled) board `board`.
+///
+/// Empty fields are no violation, but:
+/// - every three equal adjacent fields are reported as `Triple`
+///   (i.e. four equal fields are reported as two triples),
+/// - rows and columns with more than half fields of the same token
+///   are reported as unbalanced,
+/// - every pair of equal complete rows or columns is reported as duplicate.
+///
+/// Hence, a full board is valid if and only if `check_board` returns
+/// no violations.
+pub fn check_board(board: &Board) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    find_triples(board, &mut violations);
+    find_unbalanced_lines(board, &mut violations);
+    find_duplicate_lines(board, &mut violations);
+    violations
 }
 
 #[cfg(test)]
@@ -255,7 +418,7 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(true, is_valid_pair_rule(&board, 0, 2));
+        assert!(is_valid_pair_rule(&board, 0, 2));
     }
 
     #[test]
@@ -268,7 +431,7 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(true, is_valid_pair_rule(&board, 1, 0));
+        assert!(is_valid_pair_rule(&board, 1, 0));
     }
 
     #[test]
@@ -281,9 +444,9 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(false, is_valid_pair_rule(&board, 0, 1));
-        assert_eq!(false, is_valid_pair_rule(&board, 1, 1));
-        assert_eq!(false, is_valid_pair_rule(&board, 2, 1));
+        assert!(!is_valid_pair_rule(&board, 0, 1));
+        assert!(!is_valid_pair_rule(&board, 1, 1));
+        assert!(!is_valid_pair_rule(&board, 2, 1));
     }
 
     #[test]
@@ -296,9 +459,9 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(false, is_valid_pair_rule(&board, 1, 0));
-        assert_eq!(false, is_valid_pair_rule(&board, 1, 1));
-        assert_eq!(false, is_valid_pair_rule(&board, 1, 2));
+        assert!(!is_valid_pair_rule(&board, 1, 0));
+        assert!(!is_valid_pair_rule(&board, 1, 1));
+        assert!(!is_valid_pair_rule(&board, 1, 2));
     }
 
     #[test]
@@ -311,7 +474,7 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(true, is_valid_colum(&board, 1, 2));
+        assert!(is_valid_colum(&board, 1, 2));
     }
 
     #[test]
@@ -324,7 +487,7 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(false, is_valid_colum(&board, 1, 2));
+        assert!(!is_valid_colum(&board, 1, 2));
     }
 
     #[test]
@@ -338,7 +501,7 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(true, is_valid_row(&board, 2, 1));
+        assert!(is_valid_row(&board, 2, 1));
     }
 
     #[test]
@@ -352,7 +515,7 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(false, is_valid_row(&board, 2, 1));
+        assert!(!is_valid_row(&board, 2, 1));
     }
 
     #[test]
@@ -366,10 +529,10 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(Some(0), calc_column_siganture(&board, 0));
-        assert_eq!(Some(10), calc_column_siganture(&board, 1));
-        assert_eq!(Some(3), calc_column_siganture(&board, 2));
-        assert_eq!(Some(15), calc_column_siganture(&board, 3));
+        assert_eq!(Some(&[0][..]), calc_column_siganture(&board, 0));
+        assert_eq!(Some(&[10][..]), calc_column_siganture(&board, 1));
+        assert_eq!(Some(&[3][..]), calc_column_siganture(&board, 2));
+        assert_eq!(Some(&[15][..]), calc_column_siganture(&board, 3));
     }
 
     #[test]
@@ -396,8 +559,8 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(true, is_unique_column(&board, 0));
-        assert_eq!(true, is_unique_column(&board, 1));
+        assert!(is_unique_column(&board, 0));
+        assert!(is_unique_column(&board, 1));
     }
 
     #[test]
@@ -409,8 +572,8 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(false, is_unique_column(&board, 0));
-        assert_eq!(false, is_unique_column(&board, 1));
+        assert!(!is_unique_column(&board, 0));
+        assert!(!is_unique_column(&board, 1));
     }
 
     #[test]
@@ -422,8 +585,8 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(true, is_unique_column(&board, 0));
-        assert_eq!(true, is_unique_column(&board, 1));
+        assert!(is_unique_column(&board, 0));
+        assert!(is_unique_column(&board, 1));
     }
 
     #[test]
@@ -452,10 +615,10 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(Some(12), calc_row_siganture(&board, 0));
-        assert_eq!(Some(10), calc_row_siganture(&board, 1));
-        assert_eq!(Some(8), calc_row_siganture(&board, 2));
-        assert_eq!(Some(15), calc_row_siganture(&board, 3));
+        assert_eq!(Some(&[12][..]), calc_row_siganture(&board, 0));
+        assert_eq!(Some(&[10][..]), calc_row_siganture(&board, 1));
+        assert_eq!(Some(&[8][..]), calc_row_siganture(&board, 2));
+        assert_eq!(Some(&[15][..]), calc_row_siganture(&board, 3));
     }
     #[test]
     fn unique_row() {
@@ -466,8 +629,8 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(true, is_unique_row(&board, 0));
-        assert_eq!(true, is_unique_row(&board, 1));
+        assert!(is_unique_row(&board, 0));
+        assert!(is_unique_row(&board, 1));
     }
 
     #[test]
@@ -483,7 +646,7 @@ mod tests {
 
         for x in 0..4 {
             for y in 0..4 {
-                assert_eq!(true, is_move_valid(&board, x, y));
+                assert!(is_move_valid(&board, x, y));
             }
         }
     }
@@ -501,8 +664,8 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(false, is_unique_column(&board, 2));
-        assert_eq!(false, is_unique_column(&board, 5));
+        assert!(!is_unique_column(&board, 2));
+        assert!(!is_unique_column(&board, 5));
     }
 
     #[test]
@@ -518,11 +681,11 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(false, is_unique_row(&board, 0));
-        assert_eq!(false, is_unique_row(&board, 2));
+        assert!(!is_unique_row(&board, 0));
+        assert!(!is_unique_row(&board, 2));
 
-        assert_eq!(false, is_unique_row(&board, 1));
-        assert_eq!(false, is_unique_row(&board, 3));
+        assert!(!is_unique_row(&board, 1));
+        assert!(!is_unique_row(&board, 3));
     }
 
     #[test]
@@ -536,7 +699,7 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(true, is_board_valid(&ok));
+        assert!(is_board_valid(&ok));
     }
 
     #[test]
@@ -550,7 +713,7 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(false, is_board_valid(&wrong));
+        assert!(!is_board_valid(&wrong));
     }
 
     #[test]
@@ -562,7 +725,7 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(false, are_rows_unique(&wrong));
+        assert!(!are_rows_unique(&wrong));
     }
 
     #[test]
@@ -574,7 +737,7 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(false, are_columns_unique(&wrong));
+        assert!(!are_columns_unique(&wrong));
     }
 
     #[test]
@@ -588,7 +751,7 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(false, are_rows_balanced(&wrong));
+        assert!(!are_rows_balanced(&wrong));
     }
 
     #[test]
@@ -602,7 +765,7 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(false, are_columns_balanced(&wrong));
+        assert!(!are_columns_balanced(&wrong));
     }
 
     #[test]
@@ -616,7 +779,7 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(false, is_board_valid(&incomplete));
+        assert!(!is_board_valid(&incomplete));
     }
 
     #[test]
@@ -630,7 +793,7 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(false, has_no_more_than_two_same_neightbors(&wrong));
+        assert!(!has_no_more_than_two_same_neightbors(&wrong));
     }
 
     #[test]
@@ -643,7 +806,7 @@ mod tests {
             O X X O",
         )
         .unwrap();
-        assert_eq!(true, is_board_full(&full_board));
+        assert!(is_board_full(&full_board));
     }
 
     #[test]
@@ -656,11 +819,11 @@ mod tests {
             O X X O",
         )
         .unwrap();
-        assert_eq!(false, is_board_full(&none_full_board));
+        assert!(!is_board_full(&none_full_board));
     }
 
     #[test]
-    fn has_no_more_than_two_same_neightbors_vert_ignores_empty() {
+    fn triple_rule_ignores_empty_vertically() {
         let invalid_board = Board::from_str(
             " _ _ _ _
               X _ _ _
@@ -669,15 +832,13 @@ mod tests {
             ",
         )
         .unwrap();
-        assert!(has_no_more_than_two_same_neightbors_vert(
-            &invalid_board,
-            3,
-            1
-        ));
+        let (_, o) = invalid_board.column_masks(0);
+        assert!(!has_triple_with(o, 0));
+        assert!(!has_triple(o));
     }
 
     #[test]
-    fn has_no_more_than_two_same_neightbors_vert_finds_errors() {
+    fn triple_rule_finds_errors_vertically() {
         let invalid_board = Board::from_str(
             " _ _ _ _
               X _ _ _
@@ -686,20 +847,14 @@ mod tests {
             ",
         )
         .unwrap();
-        assert!(has_no_more_than_two_same_neightbors_vert(
-            &invalid_board,
-            0,
-            1
-        ));
-        assert!(!has_no_more_than_two_same_neightbors_vert(
-            &invalid_board,
-            0,
-            2
-        ));
+        let (x, _) = invalid_board.column_masks(0);
+        assert!(has_triple_with(x, 1));
+        assert!(has_triple_with(x, 3));
+        assert!(!is_valid_pair_rule(&invalid_board, 0, 2));
     }
 
     #[test]
-    fn has_no_more_than_two_same_neightbors_horz_ignores_empty() {
+    fn triple_rule_ignores_empty_horizontally() {
         let invalid_board = Board::from_str(
             " _ _ _ _
               _ _ _ _
@@ -708,15 +863,13 @@ mod tests {
             ",
         )
         .unwrap();
-        assert!(has_no_more_than_two_same_neightbors_horz(
-            &invalid_board,
-            1,
-            1
-        ));
+        let (x, o) = invalid_board.row_masks(1);
+        assert!(!has_triple(x));
+        assert!(!has_triple(o));
     }
 
     #[test]
-    fn has_no_more_than_two_same_neightbors_horz_finds_errors() {
+    fn triple_rule_finds_errors_horizontally() {
         let invalid_board = Board::from_str(
             " _ _ _ _
               _ _ _ _
@@ -725,16 +878,10 @@ mod tests {
             ",
         )
         .unwrap();
-        assert!(has_no_more_than_two_same_neightbors_horz(
-            &invalid_board,
-            1,
-            2
-        ));
-        assert!(!has_no_more_than_two_same_neightbors_horz(
-            &invalid_board,
-            2,
-            2
-        ));
+        let (x, _) = invalid_board.row_masks(2);
+        assert!(has_triple_with(x, 1));
+        assert!(!has_triple_with(x, 0));
+        assert!(!is_valid_pair_rule(&invalid_board, 2, 2));
     }
 
     #[test]
@@ -763,6 +910,27 @@ mod tests {
         assert!(!has_no_more_than_two_same_neightbors(&invalid_board));
     }
 
+    #[test]
+    fn can_complete_line_with_balance() {
+        use Field::*;
+
+        assert!(can_complete_line(&[X, Empty, Empty, O]));
+        assert!(can_complete_line(&[X, Empty, X, Empty, X, Empty]));
+        assert!(!can_complete_line(&[X, X, Empty, Empty, Empty, X]));
+        assert!(!can_complete_line(&[O, Empty, Empty, Empty, O, O]));
+    }
+
+    #[test]
+    fn count_line_completions_of_lines() {
+        use Field::*;
+
+        assert_eq!(6, count_line_completions(&[Empty; 4]));
+        assert_eq!(14, count_line_completions(&[Empty; 6]));
+        assert_eq!(2, count_line_completions(&[X, Empty, Empty, O]));
+        assert_eq!(1, count_line_completions(&[X, O, O, X]));
+        assert_eq!(0, count_line_completions(&[X, X, Empty, Empty, Empty, X]));
+    }
+
     #[test]
     fn is_board_valid_issue_1() {
         let invalid_board = Board::from_str(
@@ -782,4 +950,242 @@ mod tests {
         assert!(!has_no_more_than_two_same_neightbors(&invalid_board));
         assert!(!is_board_valid(&invalid_board));
     }
+
+    #[test]
+    fn check_valid_board() {
+        let board = Board::from_str(
+            "
+            X O X O
+            O X O X
+            X X O O
+            O O X X",
+        )
+        .unwrap();
+
+        assert_eq!(Vec::<Violation>::new(), check_board(&board));
+    }
+
+    #[test]
+    fn check_empty_board() {
+        assert_eq!(Vec::<Violation>::new(), check_board(&Board::new(6)));
+    }
+
+    #[test]
+    fn check_board_with_triples() {
+        let board = Board::from_str(
+            "
+            X X X X
+            _ _ _ O
+            _ _ _ O
+            _ _ _ O",
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                Violation::Triple([(0, 0), (1, 0), (2, 0)]),
+                Violation::Triple([(1, 0), (2, 0), (3, 0)]),
+                Violation::Triple([(3, 1), (3, 2), (3, 3)]),
+                Violation::UnbalancedRow {
+                    y: 0,
+                    field: Field::X
+                },
+                Violation::UnbalancedColumn {
+                    x: 3,
+                    field: Field::O
+                },
+            ],
+            check_board(&board)
+        );
+    }
+
+    #[test]
+    fn check_board_with_duplicates() {
+        let board = Board::from_str(
+            "
+            X O
+            X O",
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                Violation::UnbalancedColumn {
+                    x: 0,
+                    field: Field::X
+                },
+                Violation::UnbalancedColumn {
+                    x: 1,
+                    field: Field::O
+                },
+                Violation::DuplicateRows(0, 1),
+            ],
+            check_board(&board)
+        );
+    }
+
+    #[test]
+    fn check_board_with_duplicate_columns() {
+        let board = Board::from_str(
+            "
+            X O X O O X
+            O X O X X O
+            X O X O O X
+            O X O X X O
+            X X O X O O
+            O O X O X X",
+        )
+        .unwrap();
+
+        let violations = check_board(&board);
+
+        assert!(violations.contains(&Violation::DuplicateColumns(2, 5)));
+        assert!(violations.contains(&Violation::DuplicateRows(0, 2)));
+        assert!(violations.contains(&Violation::DuplicateRows(1, 3)));
+    }
+
+    #[test]
+    fn violation_positions() {
+        assert_eq!(
+            vec![(0, 1), (1, 1), (0, 3), (1, 3)],
+            Violation::DuplicateRows(1, 3).positions(&Board::new(2))
+        );
+        assert_eq!(
+            vec![(2, 0), (2, 1)],
+            Violation::UnbalancedColumn {
+                x: 2,
+                field: Field::O
+            }
+            .positions(&Board::new(2))
+        );
+    }
+
+    #[test]
+    fn violation_to_string() {
+        assert_eq!(
+            "Row 2 has more O than X.",
+            Violation::UnbalancedRow {
+                y: 1,
+                field: Field::O
+            }
+            .to_string()
+        );
+        assert_eq!(
+            "Column 1 is equal to column 4.",
+            Violation::DuplicateColumns(0, 3).to_string()
+        );
+    }
+
+    fn large_board_with_rows(size: usize, row0: &[Field], row1: &[Field]) -> Board {
+        let mut board = Board::new(size);
+        for x in 0..size {
+            board.set(x, 0, row0[x]);
+            board.set(x, 1, row1[x]);
+        }
+        board
+    }
+
+    #[test]
+    fn rows_of_large_board_differ_in_last_field() {
+        let size = 66;
+        let row0 = (0..size)
+            .map(|x| if x % 2 == 0 { Field::X } else { Field::O })
+            .collect::<Vec<Field>>();
+        let mut row1 = row0.clone();
+        row1[size - 2] = Field::O;
+        row1[size - 1] = Field::X;
+        let board = large_board_with_rows(size, &row0, &row1);
+
+        assert!(is_unique_row(&board, 0));
+        assert!(is_unique_row(&board, 1));
+        assert_ne!(calc_row_siganture(&board, 0), calc_row_siganture(&board, 1));
+    }
+
+    #[test]
+    fn equal_rows_of_large_board() {
+        let size = 130;
+        let row = (0..size)
+            .map(|x| if x % 4 < 2 { Field::X } else { Field::O })
+            .collect::<Vec<Field>>();
+        let board = large_board_with_rows(size, &row, &row);
+
+        assert!(!is_unique_row(&board, 0));
+        assert!(!is_unique_row(&board, 1));
+        assert!(!are_rows_unique(&board));
+        assert!(check_board(&board).contains(&Violation::DuplicateRows(0, 1)));
+    }
+
+    #[test]
+    fn signature_of_long_line() {
+        let mut board = Board::with_dimensions(66, 2);
+        for x in 0..66 {
+            board.set(
+                x,
+                0,
+                if 0 == x || 65 == x {
+                    Field::X
+                } else {
+                    Field::O
+                },
+            );
+        }
+
+        assert_eq!(Some(&[1, 2][..]), calc_row_siganture(&board, 0));
+        assert_eq!(None, calc_row_siganture(&board, 1));
+    }
+
+    #[test]
+    fn triples_in_bit_masks() {
+        assert!(!has_triple(&[0b1101_1011]));
+        assert!(has_triple(&[0b0111_0000]));
+        // triple across two words
+        assert!(has_triple(&[3 << 62, 1]));
+        assert!(has_triple(&[1 << 63, 3]));
+        assert!(!has_triple(&[1 << 63, 2]));
+    }
+
+    #[test]
+    fn valid_rectangular_board() {
+        let board = Board::from_str(
+            "
+            X X O X O O
+            X O X O X O
+            O X O O X X
+            O O X X O X",
+        )
+        .unwrap();
+
+        assert!(is_board_full(&board));
+        assert!(is_board_valid(&board));
+        assert_eq!(Vec::<Violation>::new(), check_board(&board));
+    }
+
+    #[test]
+    fn rectangular_board_balances_rows_and_columns_separately() {
+        let board = Board::from_str(
+            "
+            X X O X O O
+            O X _ _ _ _
+            X O _ _ _ _
+            O X X O X X",
+        )
+        .unwrap();
+
+        assert!(is_valid_row(&board, 0, 0));
+        assert!(!is_valid_row(&board, 1, 3));
+        assert!(!is_valid_colum(&board, 1, 1));
+        assert_eq!(
+            vec![
+                Violation::UnbalancedRow {
+                    y: 3,
+                    field: Field::X
+                },
+                Violation::UnbalancedColumn {
+                    x: 1,
+                    field: Field::X
+                },
+            ],
+            check_board(&board)
+        );
+    }
 }
@@ -0,0 +1,311 @@
+//! Implements a backtracking solver for partially filled boards:
+//! `fn` [`solve`](fn.solve.html).
+//! It can also count the solutions of a board:
+//! `fn` [`count_solutions`](fn.count_solutions.html).
+//!
+//! The solver fills all fields, which have only one possible move
+//! (see `fn` [`calc_possible_moves`](../possible_move/fn.calc_possible_moves.html)).
+//! Only if no such field is left, it guesses a field and backtracks
+//! if the guess leads to an invalid board.
+
+use crate::bruteforce::possible_move::{calc_possible_moves, PossibleMove};
+use crate::bruteforce::rules::{can_complete_line, is_board_valid, is_move_valid};
+use crate::field::{Board, Field};
+
+/// Result of filling all forced fields of a board.
+enum Propagation {
+    /// board cannot be completed to a valid board
+    Contradiction,
+    /// board is full and valid
+    Solved,
+    /// no more forced fields, field at column `x` and row `y` needs a guess
+    Guess(usize, usize),
+}
+
+/// Returns whether all non-empty fields of `board` fullfill the binoxxo rules.
+fn is_consistent(board: &Board) -> bool {
+    let height = board.get_height();
+    (0..board.get_width())
+        .all(|x| (0..height).all(|y| Field::Empty == board.get(x, y) || is_move_valid(board, x, y)))
+}
+
+/// Returns whether all rows and columns of `board` can still be completed.
+fn can_complete_lines(board: &Board) -> bool {
+    let width = board.get_width();
+    let height = board.get_height();
+    (0..height).all(|y| {
+        let row = (0..width).map(|x| board.get(x, y)).collect::<Vec<Field>>();
+        can_complete_line(&row)
+    }) && (0..width).all(|x| {
+        let column = (0..height).map(|y| board.get(x, y)).collect::<Vec<Field>>();
+        can_complete_line(&column)
+    })
+}
+
+/// Returns the field of `candidates` with the fewest empty fields in its
+/// row and column.
+/// Guessing there completes rows and columns early, so that
+/// wrong guesses are detected early, too.
+fn choose_guess(board: &Board, candidates: &[(usize, usize)]) -> (usize, usize) {
+    let empty_in_column = |x: usize| {
+        (0..board.get_height())
+            .filter(|y| Field::Empty == board.get(x, *y))
+            .count()
+    };
+    let empty_in_row = |y: usize| {
+        (0..board.get_width())
+            .filter(|x| Field::Empty == board.get(*x, y))
+            .count()
+    };
+    *candidates
+        .iter()
+        .min_by_key(|(x, y)| empty_in_column(*x) + empty_in_row(*y))
+        .unwrap()
+}
+
+/// Fills all fields of `board`, which have only one possible move,
+/// until no such field is left.
+fn propagate(board: &mut Board) -> Propagation {
+    loop {
+        let possible_moves = calc_possible_moves(board);
+        if possible_moves.is_empty() {
+            return if is_board_valid(board) {
+                Propagation::Solved
+            } else {
+                Propagation::Contradiction
+            };
+        }
+
+        let mut guesses = Vec::new();
+        let mut progress = false;
+        for possible_move in possible_moves {
+            match possible_move {
+                PossibleMove::NoMove => return Propagation::Contradiction,
+                PossibleMove::OneMove(x, y, field) => {
+                    // the other forced moves set in this loop may already
+                    // rule out this move, too
+                    board.set(x, y, field);
+                    if !is_move_valid(board, x, y) {
+                        return Propagation::Contradiction;
+                    }
+                    progress = true;
+                }
+                PossibleMove::TwoMoves(x, y) => guesses.push((x, y)),
+            }
+        }
+
+        if !progress {
+            if !can_complete_lines(board) {
+                return Propagation::Contradiction;
+            }
+            let (x, y) = choose_guess(board, &guesses);
+            return Propagation::Guess(x, y);
+        }
+    }
+}
+
+/// Collects solutions of `board` into `solutions` until
+/// `limit` solutions are found.
+fn search(mut board: Board, limit: usize, solutions: &mut Vec<Board>) {
+    match propagate(&mut board) {
+        Propagation::Contradiction => (),
+        Propagation::Solved => solutions.push(board),
+        Propagation::Guess(x, y) => {
+            for field in &[Field::X, Field::O] {
+                if solutions.len() >= limit {
+                    break;
+                }
+                let mut guessed = board.clone();
+                guessed.set(x, y, *field);
+                search(guessed, limit, solutions);
+            }
+        }
+    }
+}
+
+/// Returns up to `limit` different solutions of `board`.
+fn find_solutions(board: &Board, limit: usize) -> Vec<Board> {
+    let mut solutions = Vec::new();
+    if 0 < limit && is_consistent(board) {
+        search(board.clone(), limit, &mut solutions);
+    }
+    solutions
+}
+
+/// Returns a full and valid board, which keeps all non-empty fields
+/// of the (partially filled) board `board`.
+///
+/// Returns `None` if there is no such board.
+/// If there are several solutions, any one of them is returned.
+pub fn solve(board: &Board) -> Option<Board> {
+    find_solutions(board, 1).pop()
+}
+
+/// Returns the number of solutions of `board`, but at most `limit`.
+///
+/// The search stops as soon as `limit` solutions are found.
+/// Hence, a small `limit` keeps the search fast even for large boards
+/// with many solutions.
+pub fn count_solutions(board: &Board, limit: usize) -> usize {
+    find_solutions(board, limit).len()
+}
+
+/// Returns whether `board` has exactly one solution.
+pub fn has_unique_solution(board: &Board) -> bool {
+    1 == count_solutions(board, 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bruteforce::build_board::create_puzzle_board;
+    use crate::rules::is_board_full;
+    use std::str::FromStr;
+
+    fn keeps_fields(puzzle: &Board, solution: &Board) -> bool {
+        (0..puzzle.get_width()).all(|x| {
+            (0..puzzle.get_height())
+                .all(|y| Field::Empty == puzzle.get(x, y) || puzzle.get(x, y) == solution.get(x, y))
+        })
+    }
+
+    #[test]
+    fn solve_empty_board() {
+        let board = Board::new(6);
+
+        let solution = solve(&board).unwrap();
+
+        assert!(is_board_full(&solution));
+        assert!(is_board_valid(&solution));
+    }
+
+    #[test]
+    fn solve_full_valid_board_returns_it() {
+        let board = Board::from_str(
+            "
+            X O X O
+            O X O X
+            X X O O
+            O O X X",
+        )
+        .unwrap();
+
+        assert_eq!(Some(board.clone()), solve(&board));
+    }
+
+    #[test]
+    fn solve_needs_backtracking() {
+        let board = Board::from_str(
+            "
+            _ _ _ _ _ _
+            _ _ X _ _ _
+            _ _ _ _ O _
+            _ X _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ O _ _",
+        )
+        .unwrap();
+
+        let solution = solve(&board).unwrap();
+
+        assert!(is_board_valid(&solution));
+        assert!(keeps_fields(&board, &solution));
+    }
+
+    #[test]
+    fn solve_generated_puzzle() {
+        let puzzle = create_puzzle_board(10, 15);
+
+        let solution = solve(&puzzle).unwrap();
+
+        assert!(is_board_valid(&solution));
+        assert!(keeps_fields(&puzzle, &solution));
+    }
+
+    #[test]
+    fn solve_unsolvable_board() {
+        let board = Board::from_str(
+            "
+            X _ _ X
+            _ _ _ _
+            X _ _ X
+            _ _ _ _",
+        )
+        .unwrap();
+
+        assert_eq!(None, solve(&board));
+    }
+
+    #[test]
+    fn solve_board_with_invalid_fields() {
+        let board = Board::from_str(
+            "
+            X X X _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _",
+        )
+        .unwrap();
+
+        assert_eq!(None, solve(&board));
+    }
+
+    #[test]
+    fn count_solutions_of_full_board() {
+        let board = Board::from_str(
+            "
+            X O X O
+            O X O X
+            X X O O
+            O O X X",
+        )
+        .unwrap();
+
+        assert_eq!(1, count_solutions(&board, 10));
+        assert!(has_unique_solution(&board));
+    }
+
+    #[test]
+    fn count_solutions_of_unsolvable_board() {
+        let board = Board::from_str(
+            "
+            X _ _ X
+            _ _ _ _
+            X _ _ X
+            _ _ _ _",
+        )
+        .unwrap();
+
+        assert_eq!(0, count_solutions(&board, 10));
+        assert!(!has_unique_solution(&board));
+    }
+
+    #[test]
+    fn count_solutions_of_empty_board() {
+        // there are 72 valid 4 x 4 boards
+        assert_eq!(72, count_solutions(&Board::new(4), 100));
+    }
+
+    #[test]
+    fn count_solutions_stops_at_limit() {
+        assert_eq!(5, count_solutions(&Board::new(10), 5));
+        assert_eq!(0, count_solutions(&Board::new(4), 0));
+        assert!(!has_unique_solution(&Board::new(10)));
+    }
+
+    #[test]
+    fn unique_solution() {
+        let board = Board::from_str(
+            "
+            O O _ _
+            O _ _ _
+            _ _ X _
+            _ _ _ _",
+        )
+        .unwrap();
+
+        assert!(has_unique_solution(&board));
+    }
+}
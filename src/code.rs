@@ -0,0 +1,528 @@
+//! This module implements short share codes for boards, e.g. for URLs or
+//! QR codes: `fn` [`Board::to_code`](../field/struct.Board.html#method.to_code)
+//! and `fn` [`Board::from_code`](../field/struct.Board.html#method.from_code).
+//!
+//! A share code packs the board into bytes and writes them with the URL safe
+//! base 64 alphabet (`A-Z`, `a-z`, `0-9`, `-` and `_`):
+//!
+//! - format version (1 byte)
+//! - width and height (each as variable length number)
+//! - which fields are given (1 byte: none, all filled fields or a mask)
+//! - 2 bits per field row by row: `00` empty, `01` X, `10` O
+//! - if needed: 1 bit per filled field, whether it is given
+//! - CRC-16 checksum of all bytes before it (2 bytes)
+//!
+//! A 10 x 10 board needs 42 characters.
+
+use crate::field::{Board, Field};
+
+use std::error::Error;
+use std::fmt;
+
+/// Version of the share code format.
+const VERSION: u8 = 1;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// No field is given.
+const GIVEN_NONE: u8 = 0;
+/// All fields, which are not `Empty`, are given.
+const GIVEN_FILLED: u8 = 1;
+/// A bit mask of the filled fields follows the fields.
+const GIVEN_MASK: u8 = 2;
+
+const EMPTY_BITS: u8 = 0b00;
+const X_BITS: u8 = 0b01;
+const O_BITS: u8 = 0b10;
+
+const CHECKSUM_BYTES: usize = 2;
+
+/// Reasons why a share code cannot be read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CodeError {
+    /// the code contains a character outside of the share code alphabet
+    InvalidCharacter(char),
+    /// the code ends before the board is complete
+    TooShort,
+    /// the code was written by another version of the format
+    UnsupportedVersion(u8),
+    /// the checksum does not match, the code was changed or mistyped
+    ChecksumMismatch,
+    /// board size (width or height) is odd or zero
+    InvalidSize(usize),
+    /// the code contains a value, which is not a valid field or given mode,
+    /// data after the board or padding bits, which are not zero
+    InvalidData,
+}
+
+impl fmt::Display for CodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CodeError::InvalidCharacter(c) => write!(f, "invalid character '{}' in code", c),
+            CodeError::TooShort => f.write_str("code is too short"),
+            CodeError::UnsupportedVersion(version) => write!(
+                f,
+                "code has version {}, but only version {} is supported",
+                version, VERSION
+            ),
+            CodeError::ChecksumMismatch => f.write_str("code is corrupted (wrong checksum)"),
+            CodeError::InvalidSize(size) => {
+                write!(f, "board size {} must be even and larger than zero", size)
+            }
+            CodeError::InvalidData => f.write_str("code contains invalid data"),
+        }
+    }
+}
+
+impl Error for CodeError {}
+
+/// CRC-16/CCITT-FALSE of `bytes`.
+fn crc16(bytes: &[u8]) -> u16 {
+    let mut crc = 0xffffu16;
+    for byte in bytes {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if 0 != crc & 0x8000 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+fn push_number(bytes: &mut Vec<u8>, mut number: usize) {
+    while number >= 0x80 {
+        bytes.push(0x80 | (number & 0x7f) as u8);
+        number >>= 7;
+    }
+    bytes.push(number as u8);
+}
+
+fn read_number<I: Iterator<Item = u8>>(bytes: &mut I) -> Result<usize, CodeError> {
+    let mut number = 0usize;
+    for shift in (0..usize::BITS).step_by(7) {
+        let byte = bytes.next().ok_or(CodeError::TooShort)?;
+        number |= ((byte & 0x7f) as usize) << shift;
+        if 0 == byte & 0x80 {
+            return Ok(number);
+        }
+    }
+    Err(CodeError::InvalidData)
+}
+
+/// Appends values with a few bits to bytes, lowest bit first.
+struct BitWriter {
+    bytes: Vec<u8>,
+    bits: usize,
+}
+
+impl BitWriter {
+    fn new(bytes: Vec<u8>) -> BitWriter {
+        BitWriter { bytes, bits: 0 }
+    }
+
+    fn push(&mut self, value: u8, bits: usize) {
+        for i in 0..bits {
+            if 0 == self.bits % 8 {
+                self.bytes.push(0);
+            }
+            if 0 != value & (1 << i) {
+                *self.bytes.last_mut().unwrap() |= 1 << (self.bits % 8);
+            }
+            self.bits += 1;
+        }
+    }
+}
+
+/// Reads values written by `BitWriter`.
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    bit: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn read(&mut self, bits: usize) -> Result<u8, CodeError> {
+        let mut value = 0;
+        for i in 0..bits {
+            let byte = self.bytes.get(self.bit / 8).ok_or(CodeError::TooShort)?;
+            if 0 != byte & (1 << (self.bit % 8)) {
+                value |= 1 << i;
+            }
+            self.bit += 1;
+        }
+        Ok(value)
+    }
+
+    fn remaining_bits(&self) -> usize {
+        self.bytes.len() * 8 - self.bit
+    }
+
+    /// Returns whether only zero bits to fill up the last byte are left.
+    fn is_at_end(&self) -> bool {
+        let used_bytes = (self.bit + 7) / 8;
+        self.bytes.len() == used_bytes
+            && (0 == self.bit % 8 || 0 == self.bytes[used_bytes - 1] >> (self.bit % 8))
+    }
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut code = String::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for byte in bytes {
+        buffer |= (*byte as u32) << bits;
+        bits += 8;
+        while bits >= 6 {
+            code.push(ALPHABET[(buffer & 0x3f) as usize] as char);
+            buffer >>= 6;
+            bits -= 6;
+        }
+    }
+    if 0 < bits {
+        code.push(ALPHABET[(buffer & 0x3f) as usize] as char);
+    }
+    code
+}
+
+fn decode_base64(code: &str) -> Result<Vec<u8>, CodeError> {
+    let mut bytes = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in code.chars() {
+        let value = ALPHABET
+            .iter()
+            .position(|a| *a as char == c)
+            .ok_or(CodeError::InvalidCharacter(c))?;
+        buffer |= (value as u32) << bits;
+        bits += 6;
+        if bits >= 8 {
+            bytes.push((buffer & 0xff) as u8);
+            buffer >>= 8;
+            bits -= 8;
+        }
+    }
+    // `encode_base64` pads the last character with zero bits and never
+    // writes a character without bits of a byte, so that every byte
+    // sequence has exactly one code
+    if 6 <= bits || 0 != buffer {
+        return Err(CodeError::InvalidData);
+    }
+    Ok(bytes)
+}
+
+fn field_bits(field: Field) -> u8 {
+    match field {
+        Field::Empty => EMPTY_BITS,
+        Field::X => X_BITS,
+        Field::O => O_BITS,
+    }
+}
+
+fn check_size(size: usize) -> Result<(), CodeError> {
+    if 0 < size && 0 == size % 2 {
+        Ok(())
+    } else {
+        Err(CodeError::InvalidSize(size))
+    }
+}
+
+impl Board {
+    /// Returns a short share code of the board, which contains
+    /// its size, its fields and which fields are given.
+    ///
+    /// ```
+    /// use binoxxo::field::Board;
+    /// use std::str::FromStr;
+    /// let board = Board::from_str("X* _ _ _ _ _ O* _ _ _ _ _ _ X* _ _").unwrap();
+    /// let code = board.to_code();
+    /// assert_eq!(board, Board::from_code(&code).unwrap());
+    /// ```
+    pub fn to_code(&self) -> String {
+        let (width, height) = (self.get_width(), self.get_height());
+        let positions = || (0..height).flat_map(move |y| (0..width).map(move |x| (x, y)));
+        let filled = positions()
+            .filter(|(x, y)| Field::Empty != self.get(*x, *y))
+            .collect::<Vec<(usize, usize)>>();
+        let given = filled.iter().filter(|(x, y)| self.is_given(*x, *y)).count();
+        let given_mode = if 0 == given {
+            GIVEN_NONE
+        } else if filled.len() == given {
+            GIVEN_FILLED
+        } else {
+            GIVEN_MASK
+        };
+
+        let mut bytes = vec![VERSION];
+        push_number(&mut bytes, width);
+        push_number(&mut bytes, height);
+        bytes.push(given_mode);
+        let mut writer = BitWriter::new(bytes);
+        for (x, y) in positions() {
+            writer.push(field_bits(self.get(x, y)), 2);
+        }
+        if GIVEN_MASK == given_mode {
+            for (x, y) in filled {
+                writer.push(self.is_given(x, y) as u8, 1);
+            }
+        }
+        let mut bytes = writer.bytes;
+        let checksum = crc16(&bytes);
+        bytes.extend_from_slice(&checksum.to_be_bytes());
+        encode_base64(&bytes)
+    }
+
+    /// Reads a board from a share code written by
+    /// `fn` [`to_code`](#method.to_code).
+    ///
+    /// Returns an error, if the code contains invalid characters, was
+    /// changed (wrong checksum) or was written by another version.
+    pub fn from_code(code: &str) -> Result<Board, CodeError> {
+        let bytes = decode_base64(code.trim())?;
+        if bytes.len() < 1 + CHECKSUM_BYTES {
+            return Err(CodeError::TooShort);
+        }
+        // check the checksum first, so that a typo in the version byte is
+        // reported as checksum mismatch
+        let (bytes, checksum) = bytes.split_at(bytes.len() - CHECKSUM_BYTES);
+        if crc16(bytes).to_be_bytes() != checksum {
+            return Err(CodeError::ChecksumMismatch);
+        }
+        if VERSION != bytes[0] {
+            return Err(CodeError::UnsupportedVersion(bytes[0]));
+        }
+
+        let mut header = bytes[1..].iter().copied();
+        let width = read_number(&mut header)?;
+        let height = read_number(&mut header)?;
+        check_size(width)?;
+        check_size(height)?;
+        let given_mode = header.next().ok_or(CodeError::TooShort)?;
+        if given_mode > GIVEN_MASK {
+            return Err(CodeError::InvalidData);
+        }
+        let header_len = bytes.len() - header.len();
+        let mut reader = BitReader {
+            bytes: &bytes[header_len..],
+            bit: 0,
+        };
+        // check the length before creating a (possibly huge) board
+        if reader.remaining_bits() / 2 / width < height {
+            return Err(CodeError::TooShort);
+        }
+
+        let mut board = Board::with_dimensions(width, height);
+        let mut filled = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                let field = match reader.read(2)? {
+                    EMPTY_BITS => continue,
+                    X_BITS => Field::X,
+                    O_BITS => Field::O,
+                    _ => return Err(CodeError::InvalidData),
+                };
+                board.set(x, y, field);
+                filled.push((x, y, field));
+            }
+        }
+        for (x, y, field) in filled {
+            let given = match given_mode {
+                GIVEN_NONE => false,
+                GIVEN_FILLED => true,
+                _ => 1 == reader.read(1)?,
+            };
+            if given {
+                board.set_given(x, y, field);
+            }
+        }
+        if !reader.is_at_end() {
+            return Err(CodeError::InvalidData);
+        }
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn puzzle() -> Board {
+        Board::from_str(
+            "
+            X* _ _ _ O* _
+            _ _ O* _ _ _
+            _ _ _ _ _ X*
+            _ O* _ _ _ _",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn crc16_check_value() {
+        assert_eq!(0x29b1, crc16(b"123456789"));
+    }
+
+    #[test]
+    fn base64_round_trip() {
+        for len in 0..10 {
+            let bytes = (0..len).map(|i| (i * 37 + 200) as u8).collect::<Vec<u8>>();
+
+            assert_eq!(bytes, decode_base64(&encode_base64(&bytes)).unwrap());
+        }
+        assert_eq!("AABI", encode_base64(&[0, 0x10, 0x20]));
+    }
+
+    #[test]
+    fn base64_padding_must_be_zero() {
+        assert_eq!(Ok(vec![0x10]), decode_base64("QA"));
+        assert_eq!(Err(CodeError::InvalidData), decode_base64("QQ"));
+        assert_eq!(Err(CodeError::InvalidData), decode_base64("AAAAA"));
+    }
+
+    #[test]
+    fn numbers() {
+        for number in &[0, 1, 127, 128, 300, 1 << 20] {
+            let mut bytes = Vec::new();
+            push_number(&mut bytes, *number);
+
+            assert_eq!(Ok(*number), read_number(&mut bytes.into_iter()));
+        }
+        assert_eq!(
+            Err(CodeError::TooShort),
+            read_number(&mut vec![0x80].into_iter())
+        );
+    }
+
+    #[test]
+    fn bits() {
+        let mut writer = BitWriter::new(vec![0xff]);
+        writer.push(0b10, 2);
+        writer.push(0b1, 1);
+        writer.push(0b01, 2);
+        assert_eq!(vec![0xff, 0b01110], writer.bytes);
+
+        let mut reader = BitReader {
+            bytes: &writer.bytes[1..],
+            bit: 0,
+        };
+        assert_eq!(Ok(0b10), reader.read(2));
+        assert_eq!(Ok(0b011), reader.read(3));
+        assert!(reader.is_at_end());
+        assert_eq!(Ok(0), reader.read(3));
+        assert!(reader.is_at_end());
+        assert_eq!(Err(CodeError::TooShort), reader.read(1));
+
+        let reader = BitReader {
+            bytes: &[0b100, 0],
+            bit: 2,
+        };
+        assert!(!reader.is_at_end());
+    }
+
+    #[test]
+    fn round_trip() {
+        let puzzle = puzzle();
+        let mut partly_given = puzzle.clone();
+        partly_given.set(1, 1, Field::X);
+        let mut not_given = puzzle.clone();
+        not_given.unmark_given();
+
+        for board in &[puzzle, partly_given, not_given, Board::new(2)] {
+            assert_eq!(*board, Board::from_code(&board.to_code()).unwrap());
+        }
+    }
+
+    #[test]
+    fn large_board() {
+        let mut board = Board::with_dimensions(200, 2);
+        board.set(199, 1, Field::O);
+
+        assert_eq!(board, Board::from_code(&board.to_code()).unwrap());
+    }
+
+    #[test]
+    fn code_is_short() {
+        let board = crate::bruteforce::create_puzzle_board(10, 15);
+
+        assert_eq!(42, board.to_code().len());
+    }
+
+    #[test]
+    fn typo_in_version() {
+        let code = puzzle().to_code();
+
+        assert_eq!(
+            Err(CodeError::ChecksumMismatch),
+            Board::from_code(&code.replacen(&code[..1], "C", 1))
+        );
+    }
+
+    #[test]
+    fn corrupted_code() {
+        let code = puzzle().to_code();
+        let mut chars = code.chars().collect::<Vec<char>>();
+        chars[7] = if 'A' == chars[7] { 'B' } else { 'A' };
+        let corrupted = chars.into_iter().collect::<String>();
+
+        assert_eq!(
+            Err(CodeError::ChecksumMismatch),
+            Board::from_code(&corrupted)
+        );
+        assert_eq!(
+            Err(CodeError::InvalidCharacter('*')),
+            Board::from_code(&code.replacen(&code[3..4], "*", 1))
+        );
+        assert_eq!(Err(CodeError::TooShort), Board::from_code(""));
+        assert_eq!(Err(CodeError::TooShort), Board::from_code(&code[..3]));
+        assert_eq!(
+            Err(CodeError::ChecksumMismatch),
+            Board::from_code(&code[..code.len() - 4])
+        );
+    }
+
+    /// Returns the code of `bytes` with checksum.
+    fn code_of(bytes: &[u8]) -> String {
+        let mut bytes = bytes.to_vec();
+        let checksum = crc16(&bytes);
+        bytes.extend_from_slice(&checksum.to_be_bytes());
+        encode_base64(&bytes)
+    }
+
+    #[test]
+    fn other_version() {
+        assert_eq!(
+            Err(CodeError::UnsupportedVersion(2)),
+            Board::from_code(&code_of(&[2, 2, 2, 0, 0]))
+        );
+    }
+
+    #[test]
+    fn invalid_data() {
+        assert_eq!(
+            Ok(Board::new(2)),
+            Board::from_code(&code_of(&[1, 2, 2, 0, 0]))
+        );
+        assert_eq!(
+            Err(CodeError::InvalidSize(3)),
+            Board::from_code(&code_of(&[1, 3, 2, 0, 0]))
+        );
+        assert_eq!(
+            Err(CodeError::InvalidData),
+            Board::from_code(&code_of(&[1, 2, 2, 3, 0]))
+        );
+        assert_eq!(
+            Err(CodeError::InvalidData),
+            Board::from_code(&code_of(&[1, 2, 2, 0, 0b11]))
+        );
+        assert_eq!(
+            Err(CodeError::InvalidData),
+            Board::from_code(&code_of(&[1, 2, 2, 0, 0, 0]))
+        );
+        assert_eq!(
+            Err(CodeError::TooShort),
+            Board::from_code(&code_of(&[1, 0xfe, 0xff, 0x7f, 2, 0, 0]))
+        );
+    }
+}
@@ -3,11 +3,18 @@
 //! - `enum` [`Field`](enum.Field.html)
 //! - `struct` [`Board`](struct.Board.html)
 
+use std::error::Error;
+use std::fmt;
 use std::str::FromStr;
-use std::string::ToString;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
 
 /// Represents on field of a binoxxo board.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Field {
     /// field is empty
     Empty,
@@ -17,26 +24,138 @@ pub enum Field {
     O,
 }
 
-/// A sqaure binoxxo board with a side length and its fields.
+/// Number of fields stored in one word of a bit mask.
+pub(crate) const MASK_BITS: usize = 64;
+
+/// Bit masks of all rows or all columns of a board.
+///
+/// Bit `i` of line `l` is set in `x` (or `o`) if field `i` of line `l`
+/// is X (or O). Every line uses `words` words, so lines of any length fit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Lines {
+    words: usize,
+    x: Vec<u64>,
+    o: Vec<u64>,
+}
+
+impl Lines {
+    fn new(num_lines: usize, len: usize) -> Lines {
+        let words = (len + MASK_BITS - 1) / MASK_BITS;
+        Lines {
+            words,
+            x: vec![0; num_lines * words],
+            o: vec![0; num_lines * words],
+        }
+    }
+
+    fn index(&self, line: usize, i: usize) -> (usize, u64) {
+        (line * self.words + i / MASK_BITS, 1 << (i % MASK_BITS))
+    }
+
+    fn get(&self, line: usize, i: usize) -> Field {
+        let (word, bit) = self.index(line, i);
+        if 0 != self.x[word] & bit {
+            Field::X
+        } else if 0 != self.o[word] & bit {
+            Field::O
+        } else {
+            Field::Empty
+        }
+    }
+
+    fn set(&mut self, line: usize, i: usize, field: Field) {
+        let (word, bit) = self.index(line, i);
+        self.x[word] &= !bit;
+        self.o[word] &= !bit;
+        match field {
+            Field::X => self.x[word] |= bit,
+            Field::O => self.o[word] |= bit,
+            Field::Empty => (),
+        }
+    }
+
+    fn masks(&self, line: usize) -> (&[u64], &[u64]) {
+        let range = (line * self.words)..((line + 1) * self.words);
+        (&self.x[range.clone()], &self.o[range])
+    }
+}
+
+/// A binoxxo board with a width, a height and its fields.
+///
+/// Most boards are square, but rectangular boards (e.g. 8 x 10) are
+/// supported, too. Width and height must be even.
+///
+/// The fields are stored as bit masks per row and per column,
+/// which makes checking the binoxxo rules cheap.
+///
+/// Fields can be *given*: the clues of a puzzle, which the player
+/// must not change. Given fields cannot be `set` or `clear`ed.
+///
+/// With the `serde` feature a board is serialized with its width,
+/// its height and its rows in the text format, e.g. in JSON:
+/// `{"width": 2, "height": 2, "rows": ["X* O", "_ X"]}`.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "BoardRepr", into = "BoardRepr")
+)]
 pub struct Board {
-    size: usize,
-    fields: Vec<Field>,
+    width: usize,
+    height: usize,
+    rows: Lines,
+    columns: Lines,
+    /// bit masks of the given fields per row (same layout as `rows`)
+    given: Vec<u64>,
 }
 
 impl Board {
-    /// Creates a new binoxxo board of side length `size`.
+    /// Creates a new square binoxxo board of side length `size`.
     ///
     /// # Panics
     ///
     /// Panics if `size` is odd or `0`.
+    /// Use `try_new` to get an error instead.
     pub fn new(size: usize) -> Board {
-        assert!(size > 1, "board size must be larger than zero");
-        assert!(0 == size % 2, "board size must be even");
+        Board::with_dimensions(size, size)
+    }
+
+    /// Creates a new square binoxxo board of side length `size` or returns
+    /// an error if `size` is odd or `0`.
+    pub fn try_new(size: usize) -> Result<Board, SizeError> {
+        Board::try_with_dimensions(size, size)
+    }
 
+    /// Creates a new binoxxo board with `width` columns and `height` rows
+    /// or returns an error if `width` or `height` is odd or `0`.
+    pub fn try_with_dimensions(width: usize, height: usize) -> Result<Board, SizeError> {
+        for &size in &[width, height] {
+            if 0 == size || 0 != size % 2 {
+                return Err(SizeError(size));
+            }
+        }
+        Ok(Board::with_dimensions(width, height))
+    }
+
+    /// Creates a new binoxxo board with `width` columns and `height` rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` or `height` is odd or `0`.
+    /// Use `try_with_dimensions` to get an error instead.
+    pub fn with_dimensions(width: usize, height: usize) -> Board {
+        assert!(width > 1, "board width must be larger than zero");
+        assert!(0 == width % 2, "board width must be even");
+        assert!(height > 1, "board height must be larger than zero");
+        assert!(0 == height % 2, "board height must be even");
+
+        let rows = Lines::new(height, width);
         Board {
-            size,
-            fields: vec![Field::Empty; size * size],
+            width,
+            height,
+            given: vec![0; rows.x.len()],
+            rows,
+            columns: Lines::new(width, height),
         }
     }
 
@@ -44,112 +163,476 @@ impl Board {
     ///
     /// # Panics
     ///
-    /// Panics if `x` or `y` are out-of-bounds (larger or equal to `get_size`).
+    /// Panics if `x` or `y` are out-of-bounds (larger or equal to
+    /// `get_width` or `get_height`).
     /// Panics if field at given coordinates is already `Empty`.
+    /// Panics if the field is given.
     pub fn clear(&mut self, x: usize, y: usize) {
-        assert!(x < self.size);
-        assert!(y < self.size);
-        assert!(Field::Empty != self.fields[x * self.size + y]);
+        assert!(x < self.width);
+        assert!(y < self.height);
+        assert!(Field::Empty != self.columns.get(x, y));
+        assert!(!self.is_given(x, y), "given fields cannot be cleared");
 
-        self.fields[x * self.size + y] = Field::Empty;
+        self.rows.set(y, x, Field::Empty);
+        self.columns.set(x, y, Field::Empty);
     }
 
     /// Sets field at column `x` and row `y` to `field`.
     ///
     /// # Panics
     ///
-    /// Panics if `x` or `y` are out-of-bounds (larger or equal to `get_size`).
+    /// Panics if `x` or `y` are out-of-bounds (larger or equal to
+    /// `get_width` or `get_height`).
     /// Panics if `field` is `Empty`.
+    /// Panics if the field is given.
     pub fn set(&mut self, x: usize, y: usize, field: Field) {
-        assert!(x < self.size);
-        assert!(y < self.size);
+        assert!(x < self.width);
+        assert!(y < self.height);
         assert!(field != Field::Empty);
+        assert!(!self.is_given(x, y), "given fields cannot be set");
 
-        self.fields[x * self.size + y] = field;
+        self.rows.set(y, x, field);
+        self.columns.set(x, y, field);
+    }
+
+    /// Sets field at column `x` and row `y` to `field` and marks it
+    /// as given. A given field may be overwritten by `set_given`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `y` are out-of-bounds (larger or equal to
+    /// `get_width` or `get_height`).
+    /// Panics if `field` is `Empty`.
+    pub fn set_given(&mut self, x: usize, y: usize, field: Field) {
+        assert!(x < self.width);
+        assert!(y < self.height);
+        assert!(field != Field::Empty);
+
+        self.rows.set(y, x, field);
+        self.columns.set(x, y, field);
+        let (word, bit) = self.rows.index(y, x);
+        self.given[word] |= bit;
+    }
+
+    /// Marks all fields, which are not `Empty`, as given.
+    ///
+    /// Use it to turn a puzzle (e.g. from
+    /// [`create_puzzle_board`](../bruteforce/fn.create_puzzle_board.html))
+    /// into the start of a game, whose clues the player cannot change.
+    pub fn mark_given(&mut self) {
+        for (given, (x, o)) in self
+            .given
+            .iter_mut()
+            .zip(self.rows.x.iter().zip(&self.rows.o))
+        {
+            *given = x | o;
+        }
+    }
+
+    /// Removes the given mark from all fields. The fields keep their content.
+    pub fn unmark_given(&mut self) {
+        self.given.iter_mut().for_each(|given| *given = 0);
+    }
+
+    /// Returns whether field at column `x` and row `y` is given.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `y` are out-of-bounds (larger or equal to
+    /// `get_width` or `get_height`).
+    pub fn is_given(&self, x: usize, y: usize) -> bool {
+        assert!(x < self.width);
+        assert!(y < self.height);
+
+        let (word, bit) = self.rows.index(y, x);
+        0 != self.given[word] & bit
     }
 
     /// Returns field at column `x` and row `y` to `field`.
     ///
     /// # Panics
     ///
-    /// Panics if `x` or `y` are out-of-bounds (larger or equal to `get_size`).
+    /// Panics if `x` or `y` are out-of-bounds (larger or equal to
+    /// `get_width` or `get_height`).
     pub fn get(&self, x: usize, y: usize) -> Field {
-        assert!(x < self.size);
-        assert!(y < self.size);
+        assert!(x < self.width);
+        assert!(y < self.height);
 
-        self.fields[x * self.size + y]
+        self.columns.get(x, y)
     }
 
     /// Returns side length of board `size`.
+    ///
+    /// For rectangular boards this is the width (number of columns),
+    /// use `get_width` and `get_height` instead.
     pub fn get_size(&self) -> usize {
-        self.size
+        self.width
+    }
+
+    /// Returns the number of columns.
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns whether width and height of the board are equal.
+    pub fn is_square(&self) -> bool {
+        self.width == self.height
+    }
+
+    /// Returns the bit masks of the X and O fields of row `y`:
+    /// bit `x` is the field in column `x`.
+    pub(crate) fn row_masks(&self, y: usize) -> (&[u64], &[u64]) {
+        assert!(y < self.height);
+
+        self.rows.masks(y)
+    }
+
+    /// Returns the bit masks of the X and O fields of column `x`:
+    /// bit `y` is the field in row `y`.
+    pub(crate) fn column_masks(&self, x: usize) -> (&[u64], &[u64]) {
+        assert!(x < self.width);
+
+        self.columns.masks(x)
     }
 }
 
 const X_STR: &str = "X";
 const O_STR: &str = "O";
 const EMPTY_STR: &str = "_";
+/// Suffix of given fields in the text format, e.g. `X*`.
+const GIVEN_SUFFIX: &str = "*";
 
-impl FromStr for Board {
-    type Err = String;
+/// Error of `Board::try_new` and `Board::try_with_dimensions`:
+/// the contained width or height is odd or zero.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SizeError(pub usize);
 
-    fn from_str(b: &str) -> Result<Board, String> {
-        let fields = b.split_whitespace().collect::<Vec<&str>>();
-        let size = (fields.len() as f64).sqrt() as usize;
-        if size * size != fields.len() {
-            return Err("Number of string elements must be square number".to_string());
-        }
-        if size < 2 {
-            return Err("Board too small (at least 2 elements)".to_string());
+impl fmt::Display for SizeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "board size {} must be even and larger than zero", self.0)
+    }
+}
+
+impl Error for SizeError {}
+
+/// Kinds of errors of parsing a board.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// the number of fields is neither a square number nor equal in every line
+    NonSquareCount,
+    /// the board has less than 2 fields per side
+    TooSmall,
+    /// width or height of the board is odd
+    OddSize,
+    /// a token is no field
+    UnknownToken,
+    /// an empty field is marked as given
+    GivenEmpty,
+    /// the symbols of a [`Notation`](../notation/struct.Notation.html) are
+    /// not distinct
+    InvalidNotation,
+}
+
+/// Error of parsing a board, e.g. with `Board::from_str`.
+///
+/// If the error is caused by a token, the error contains the token and
+/// its position in the input text. Lines and columns start at 1,
+/// columns count characters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseBoardError {
+    /// what went wrong
+    pub kind: ParseErrorKind,
+    /// the offending token
+    pub token: Option<String>,
+    /// line of the offending token or line in the input
+    pub line: Option<usize>,
+    /// column of the offending token in its line
+    pub column: Option<usize>,
+}
+
+impl ParseBoardError {
+    pub(crate) fn new(kind: ParseErrorKind) -> ParseBoardError {
+        ParseBoardError {
+            kind,
+            token: None,
+            line: None,
+            column: None,
         }
-        if 0 != size % 2 {
-            return Err("Board size must be even".to_string());
+    }
+
+    fn at_token<T: fmt::Display>(kind: ParseErrorKind, token: &Token<T>) -> ParseBoardError {
+        ParseBoardError {
+            kind,
+            token: Some(token.text.to_string()),
+            line: Some(token.line),
+            column: Some(token.column),
         }
-        let mut x = 0usize;
-        let mut y = 0usize;
-        let mut board = Board::new(size);
-        for fieldstr in fields {
-            let field;
-            if fieldstr == X_STR {
-                field = Field::X;
-            } else if fieldstr == O_STR {
-                field = Field::O;
-            } else if fieldstr == EMPTY_STR {
-                field = Field::Empty;
-            } else {
-                return Err("Unknown field string".to_string());
-            }
-            if Field::Empty != field {
-                board.set(x, y, field);
+    }
+}
+
+impl fmt::Display for ParseBoardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::NonSquareCount => {
+                f.write_str("Number of fields must be a square number or equal in every line")?
             }
-            x += 1;
-            if x >= size {
-                y += 1;
-                x = 0;
+            ParseErrorKind::TooSmall => f.write_str("Board too small (at least 2 elements)")?,
+            ParseErrorKind::OddSize => f.write_str("Board size must be even")?,
+            ParseErrorKind::UnknownToken => f.write_str("Unknown field")?,
+            ParseErrorKind::GivenEmpty => f.write_str("Empty field cannot be given")?,
+            ParseErrorKind::InvalidNotation => {
+                f.write_str("Notation symbols must differ and must not be whitespace")?
             }
         }
-        Ok(board)
+        if let Some(token) = &self.token {
+            write!(f, " '{}'", token)?;
+        }
+        if let Some(line) = self.line {
+            write!(f, " in line {}", line)?;
+        }
+        if let Some(column) = self.column {
+            write!(f, ", column {}", column)?;
+        }
+        Ok(())
     }
 }
 
-impl ToString for Board {
-    fn to_string(&self) -> String {
-        let mut result = String::new();
-        let size = self.get_size();
-        for y in 0..size {
-            for x in 0..size {
-                match self.get(x, y) {
-                    Field::X => result += X_STR,
-                    Field::O => result += O_STR,
-                    Field::Empty => result += EMPTY_STR,
+impl Error for ParseBoardError {}
+
+/// A token of a board in an input text with its position.
+pub(crate) struct Token<T> {
+    pub(crate) text: T,
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+/// Returns width and height of a board with the rows `lines`.
+///
+/// Each line is a row. Only a board on a single line is square and
+/// needs a square number of elements.
+fn calc_dimensions<T>(lines: &[Vec<Token<T>>]) -> Result<(usize, usize), ParseBoardError> {
+    if let [line] = lines {
+        let count = line.len();
+        let size = (count as f64).sqrt() as usize;
+        if size * size != count {
+            return Err(ParseBoardError::new(ParseErrorKind::NonSquareCount));
+        }
+        return Ok((size, size));
+    }
+    if let Some(line) = lines.iter().find(|line| line.len() != lines[0].len()) {
+        return Err(ParseBoardError {
+            line: Some(line[0].line),
+            ..ParseBoardError::new(ParseErrorKind::NonSquareCount)
+        });
+    }
+    Ok((lines.first().map_or(0, |line| line.len()), lines.len()))
+}
+
+/// Builds a board from the tokens of its non-empty `lines`
+/// (see `calc_dimensions`).
+///
+/// `parse_token` returns the field of a token and whether it is given
+/// or `None` if the token is no field.
+pub(crate) fn build_board<T, F>(
+    lines: Vec<Vec<Token<T>>>,
+    parse_token: F,
+) -> Result<Board, ParseBoardError>
+where
+    T: fmt::Display,
+    F: Fn(&T) -> Option<(Field, bool)>,
+{
+    let (width, height) = calc_dimensions(&lines)?;
+    if width < 2 || height < 2 {
+        return Err(ParseBoardError::new(ParseErrorKind::TooSmall));
+    }
+    if 0 != width % 2 || 0 != height % 2 {
+        return Err(ParseBoardError::new(ParseErrorKind::OddSize));
+    }
+    let mut x = 0usize;
+    let mut y = 0usize;
+    let mut board = Board::with_dimensions(width, height);
+    for token in lines.into_iter().flatten() {
+        let (field, given) = parse_token(&token.text)
+            .ok_or_else(|| ParseBoardError::at_token(ParseErrorKind::UnknownToken, &token))?;
+        if given && Field::Empty == field {
+            return Err(ParseBoardError::at_token(
+                ParseErrorKind::GivenEmpty,
+                &token,
+            ));
+        }
+        if given {
+            board.set_given(x, y, field);
+        } else if Field::Empty != field {
+            board.set(x, y, field);
+        }
+        x += 1;
+        if x >= width {
+            y += 1;
+            x = 0;
+        }
+    }
+    Ok(board)
+}
+
+/// Parses a board from a text with one row per line, e.g.:
+///
+/// ```text
+/// X O _ O
+/// _ _ O X
+/// ```
+///
+/// Given fields have the suffix `*` (e.g. `X*`), all other fields
+/// are entries of the player.
+///
+/// Each line is a row, so rectangular boards are read back as written by
+/// `to_string`. A square board may also be written on a single line.
+/// See [`Notation`](../notation/struct.Notation.html) for other notations.
+///
+/// Errors tell which token in which line is wrong:
+///
+/// ```
+/// use binoxxo::field::{Board, ParseErrorKind};
+/// use std::str::FromStr;
+/// let error = Board::from_str("X O\n_ Q").unwrap_err();
+/// assert_eq!(ParseErrorKind::UnknownToken, error.kind);
+/// assert_eq!("Unknown field 'Q' in line 2, column 3", error.to_string());
+/// ```
+impl FromStr for Board {
+    type Err = ParseBoardError;
+
+    fn from_str(b: &str) -> Result<Board, ParseBoardError> {
+        build_board(tokenize(b), parse_field_token)
+    }
+}
+
+/// Splits `text` into the whitespace separated tokens of its non-empty lines.
+fn tokenize(text: &str) -> Vec<Vec<Token<&str>>> {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| {
+            line.split_whitespace()
+                .map(|token| {
+                    let offset = token.as_ptr() as usize - line.as_ptr() as usize;
+                    Token {
+                        text: token,
+                        line: index + 1,
+                        column: line[..offset].chars().count() + 1,
+                    }
+                })
+                .collect::<Vec<Token<&str>>>()
+        })
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Parses a field with an optional given suffix, e.g. `X*`.
+fn parse_field_token(fieldstr: &&str) -> Option<(Field, bool)> {
+    let given = fieldstr.ends_with(GIVEN_SUFFIX);
+    let fieldstr = fieldstr.strip_suffix(GIVEN_SUFFIX).unwrap_or(fieldstr);
+    Some((Field::from_str(fieldstr).ok()?, given))
+}
+
+/// Parses a single field: `X`, `O` or `_`.
+///
+/// The error of an unknown field contains the field string as token,
+/// but no position.
+impl FromStr for Field {
+    type Err = ParseBoardError;
+
+    fn from_str(s: &str) -> Result<Field, ParseBoardError> {
+        match s {
+            X_STR => Ok(Field::X),
+            O_STR => Ok(Field::O),
+            EMPTY_STR => Ok(Field::Empty),
+            _ => Err(ParseBoardError {
+                token: Some(s.to_string()),
+                ..ParseBoardError::new(ParseErrorKind::UnknownToken)
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Field::X => f.write_str(X_STR),
+            Field::O => f.write_str(O_STR),
+            Field::Empty => f.write_str(EMPTY_STR),
+        }
+    }
+}
+
+/// Serialized form of a `Board`.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct BoardRepr {
+    width: usize,
+    height: usize,
+    rows: Vec<String>,
+}
+
+#[cfg(feature = "serde")]
+impl From<Board> for BoardRepr {
+    fn from(board: Board) -> BoardRepr {
+        BoardRepr {
+            width: board.width,
+            height: board.height,
+            rows: board.to_string().lines().map(String::from).collect(),
+        }
+    }
+}
+
+/// Checks the same size invariants as `Board::with_dimensions`
+/// and that the rows match width and height.
+#[cfg(feature = "serde")]
+impl TryFrom<BoardRepr> for Board {
+    type Error = String;
+
+    fn try_from(repr: BoardRepr) -> Result<Board, String> {
+        if repr.width < 2 || repr.height < 2 {
+            return Err(ParseBoardError::new(ParseErrorKind::TooSmall).to_string());
+        }
+        if 0 != repr.width % 2 || 0 != repr.height % 2 {
+            return Err(ParseBoardError::new(ParseErrorKind::OddSize).to_string());
+        }
+        if repr.rows.len() != repr.height {
+            return Err(format!(
+                "Board has {} rows, but height {}",
+                repr.rows.len(),
+                repr.height
+            ));
+        }
+        if let Some(row) = repr
+            .rows
+            .iter()
+            .find(|row| row.split_whitespace().count() != repr.width)
+        {
+            return Err(format!("Row '{}' does not have width {}", row, repr.width));
+        }
+        Board::from_str(&repr.rows.join("\n")).map_err(|error| error.to_string())
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                write!(f, "{}", self.get(x, y))?;
+                if self.is_given(x, y) {
+                    f.write_str(GIVEN_SUFFIX)?;
                 }
-                if x < size - 1 {
-                    result += " ";
+                if x < self.width - 1 {
+                    f.write_str(" ")?;
                 }
             }
-            result += "\n";
+            f.write_str("\n")?;
         }
-        result
+        Ok(())
     }
 }
 
@@ -161,8 +644,10 @@ mod tests {
     fn board_new() {
         let board = Board::new(2);
 
-        assert_eq!(2, board.size);
-        assert_eq!(vec![Field::Empty; 4], board.fields);
+        assert_eq!(2, board.width);
+        assert_eq!(2, board.height);
+        assert_eq!(vec![0; 2], board.rows.x);
+        assert_eq!(vec![0; 2], board.columns.o);
     }
 
     #[test]
@@ -173,7 +658,7 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(2, board.size);
+        assert_eq!(2, board.get_size());
         assert_eq!(Field::X, board.get(0, 0));
         assert_eq!(Field::O, board.get(1, 0));
         assert_eq!(Field::O, board.get(1, 1));
@@ -214,10 +699,10 @@ mod tests {
         board.set(0, 0, Field::X);
         board.set(1, 1, Field::O);
 
-        assert_eq!(
-            vec![Field::X, Field::Empty, Field::Empty, Field::O],
-            board.fields
-        );
+        assert_eq!(vec![0b01, 0b00], board.rows.x);
+        assert_eq!(vec![0b00, 0b10], board.rows.o);
+        assert_eq!(vec![0b01, 0b00], board.columns.x);
+        assert_eq!(vec![0b00, 0b10], board.columns.o);
     }
 
     #[test]
@@ -320,6 +805,27 @@ mod tests {
         assert_eq!("_ O\nO X\n".to_string(), board.to_string());
     }
 
+    #[test]
+    fn field_to_string() {
+        assert_eq!("X", Field::X.to_string());
+        assert_eq!("O", Field::O.to_string());
+        assert_eq!("_", Field::Empty.to_string());
+    }
+
+    #[test]
+    fn field_from_str() {
+        assert_eq!(Ok(Field::O), Field::from_str("O"));
+        assert_eq!(
+            Err(ParseBoardError {
+                kind: ParseErrorKind::UnknownToken,
+                token: Some("Y".to_string()),
+                line: None,
+                column: None,
+            }),
+            Field::from_str("Y")
+        );
+    }
+
     #[test]
     fn field_hashable() {
         use std::collections::HashMap;
@@ -331,4 +837,283 @@ mod tests {
 
         assert_eq!(h.get(&Field::X).unwrap(), &"X");
     }
+
+    #[test]
+    fn rectangular_board_new() {
+        let board = Board::with_dimensions(4, 2);
+
+        assert_eq!(4, board.get_width());
+        assert_eq!(2, board.get_height());
+        assert!(!board.is_square());
+        assert_eq!(Field::Empty, board.get(3, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn rectangular_board_with_odd_height_panics() {
+        Board::with_dimensions(4, 3);
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(Ok(Board::new(4)), Board::try_new(4));
+        assert_eq!(Err(SizeError(0)), Board::try_new(0));
+        assert_eq!(Err(SizeError(5)), Board::try_with_dimensions(4, 5));
+        assert_eq!(
+            "board size 3 must be even and larger than zero",
+            Board::try_with_dimensions(3, 4).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn get_size_of_rectangular_board_is_width() {
+        assert_eq!(2, Board::with_dimensions(2, 4).get_size());
+    }
+
+    #[test]
+    fn build_rectangular_board_from_str() {
+        let board = Board::from_str(
+            "
+            X O _ _
+            _ O X _",
+        )
+        .unwrap();
+
+        assert_eq!(4, board.get_width());
+        assert_eq!(2, board.get_height());
+        assert_eq!(Field::O, board.get(1, 0));
+        assert_eq!(Field::X, board.get(2, 1));
+        assert_eq!(Field::Empty, board.get(3, 1));
+    }
+
+    #[test]
+    fn build_square_board_from_single_line() {
+        let board = Board::from_str("X O _ _ _ _ _ O X _ _ _ _ _ _ O").unwrap();
+
+        assert_eq!((4, 4), (board.get_width(), board.get_height()));
+        assert_eq!(Field::O, board.get(3, 3));
+    }
+
+    #[test]
+    fn rectangular_board_with_square_count() {
+        let board = Board::from_str("X O _ _ _ _ _ O\nX _ _ _ _ _ _ O").unwrap();
+
+        assert_eq!((8, 2), (board.get_width(), board.get_height()));
+        assert_eq!(Field::O, board.get(7, 1));
+        assert_eq!(Field::X, board.get(0, 1));
+    }
+
+    #[test]
+    fn rectangular_round_trip() {
+        for (width, height) in &[(8, 2), (4, 16)] {
+            let mut board = Board::with_dimensions(*width, *height);
+            board.set(width - 1, 0, Field::X);
+            board.set(0, height - 1, Field::O);
+
+            assert_eq!(board, Board::from_str(&board.to_string()).unwrap());
+        }
+    }
+
+    #[test]
+    fn build_from_str_with_uneven_lines_fails() {
+        assert!(Board::from_str("X O _\n_ O X _ _").is_err());
+        assert!(Board::from_str("X O _\n_ O X").is_err());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |text: &str| Board::from_str(text).unwrap_err();
+
+        assert_eq!(
+            ParseBoardError {
+                kind: ParseErrorKind::NonSquareCount,
+                token: None,
+                line: Some(3),
+                column: None,
+            },
+            error("X O _\n\n  _ O X _ _")
+        );
+        assert_eq!(ParseErrorKind::TooSmall, error("X").kind);
+        assert_eq!(ParseErrorKind::OddSize, error("X O _\n_ O X").kind);
+        assert_eq!(
+            ParseBoardError {
+                kind: ParseErrorKind::UnknownToken,
+                token: Some("Ö".to_string()),
+                line: Some(2),
+                column: Some(6),
+            },
+            error("X  O\n  _  Ö")
+        );
+        assert_eq!(
+            "Empty field cannot be given '_*' in line 1, column 3",
+            error("X _* O _").to_string()
+        );
+    }
+
+    #[test]
+    fn rectangular_board_to_string_round_trip() {
+        let text = "X O\nO X\n_ _\nX _\n";
+        let board = Board::from_str(text).unwrap();
+
+        assert_eq!(2, board.get_width());
+        assert_eq!(4, board.get_height());
+        assert_eq!(text, board.to_string());
+    }
+
+    #[test]
+    fn board_masks() {
+        let board = Board::from_str(
+            "
+            X O _ X
+            _ X O O",
+        )
+        .unwrap();
+
+        assert_eq!((&[0b1001u64][..], &[0b0010u64][..]), board.row_masks(0));
+        assert_eq!((&[0b0010u64][..], &[0b1100u64][..]), board.row_masks(1));
+        assert_eq!((&[0b01u64][..], &[0b00u64][..]), board.column_masks(0));
+        assert_eq!((&[0b00u64][..], &[0b10u64][..]), board.column_masks(2));
+    }
+
+    #[test]
+    fn masks_of_large_board() {
+        let mut board = Board::with_dimensions(130, 2);
+
+        board.set(129, 1, Field::X);
+        board.set(64, 1, Field::O);
+
+        let (x, o) = board.row_masks(1);
+        assert_eq!(&[0, 0, 0b10], x);
+        assert_eq!(&[0, 1, 0], o);
+        assert_eq!(Field::X, board.get(129, 1));
+        assert_eq!(Field::O, board.get(64, 1));
+        assert_eq!(Field::Empty, board.get(65, 1));
+    }
+
+    #[test]
+    fn set_overwrites_field() {
+        let mut board = Board::new(2);
+
+        board.set(1, 0, Field::X);
+        board.set(1, 0, Field::O);
+
+        assert_eq!(Field::O, board.get(1, 0));
+        assert_eq!((&[0b00u64][..], &[0b10u64][..]), board.row_masks(0));
+    }
+
+    #[test]
+    fn set_given() {
+        let mut board = Board::new(2);
+
+        board.set_given(0, 0, Field::X);
+        board.set(1, 0, Field::O);
+
+        assert_eq!(Field::X, board.get(0, 0));
+        assert!(board.is_given(0, 0));
+        assert!(!board.is_given(1, 0));
+        assert!(!board.is_given(0, 1));
+
+        board.set_given(0, 0, Field::O);
+
+        assert_eq!(Field::O, board.get(0, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "given fields cannot be set")]
+    fn set_on_given_field() {
+        let mut board = Board::new(2);
+        board.set_given(0, 0, Field::X);
+
+        board.set(0, 0, Field::O);
+    }
+
+    #[test]
+    #[should_panic(expected = "given fields cannot be cleared")]
+    fn clear_on_given_field() {
+        let mut board = Board::new(2);
+        board.set_given(0, 0, Field::X);
+
+        board.clear(0, 0);
+    }
+
+    #[test]
+    fn mark_and_unmark_given() {
+        let mut board = Board::from_str(
+            "X _
+             _ O",
+        )
+        .unwrap();
+
+        board.mark_given();
+
+        assert!(board.is_given(0, 0));
+        assert!(board.is_given(1, 1));
+        assert!(!board.is_given(1, 0));
+
+        board.unmark_given();
+        board.clear(0, 0);
+
+        assert_eq!(Field::Empty, board.get(0, 0));
+    }
+
+    #[test]
+    fn given_from_str() {
+        let board = Board::from_str(
+            "X* O
+             _ O*",
+        )
+        .unwrap();
+
+        assert_eq!(Field::X, board.get(0, 0));
+        assert!(board.is_given(0, 0));
+        assert!(!board.is_given(1, 0));
+        assert!(board.is_given(1, 1));
+        assert_eq!(
+            ParseErrorKind::GivenEmpty,
+            Board::from_str("X _* O _").unwrap_err().kind
+        );
+    }
+
+    #[test]
+    fn given_round_trip() {
+        let text = "X* O _ O\n_ _ O* X\nO X _ _\n_ _ X O*\n";
+
+        let board = Board::from_str(text).unwrap();
+
+        assert_eq!(text, board.to_string());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let board = Board::from_str("X* O _ O\n_ _ O* X\n").unwrap();
+
+        let json = serde_json::to_string(&board).unwrap();
+
+        assert_eq!(
+            r#"{"width":4,"height":2,"rows":["X* O _ O","_ _ O* X"]}"#,
+            json
+        );
+        assert_eq!(board, serde_json::from_str::<Board>(&json).unwrap());
+        assert_eq!(
+            Field::O,
+            serde_json::from_str::<Field>(&serde_json::to_string(&Field::O).unwrap()).unwrap()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_checks_size() {
+        let error = |json: &str| serde_json::from_str::<Board>(json).unwrap_err().to_string();
+
+        assert!(error(r#"{"width":3,"height":2,"rows":["X O _","_ _ O"]}"#)
+            .starts_with("Board size must be even"));
+        assert!(error(r#"{"width":0,"height":0,"rows":[]}"#).starts_with("Board too small"));
+        assert!(error(r#"{"width":2,"height":4,"rows":["X O","_ _"]}"#)
+            .starts_with("Board has 2 rows, but height 4"));
+        assert!(error(r#"{"width":2,"height":2,"rows":["X O _ _",""]}"#)
+            .starts_with("Row 'X O _ _' does not have width 2"));
+        assert!(error(r#"{"width":2,"height":2,"rows":["X O","_ Y"]}"#)
+            .starts_with("Unknown field 'Y' in line 2, column 3"));
+    }
 }
@@ -3,10 +3,14 @@
 //! # How to use
 //!
 //! Add `binoxxo` to your dependencies.
+//! The optional `serde` feature implements `Serialize` and `Deserialize`
+//! for [`Board`](field/struct.Board.html), [`Field`](field/enum.Field.html),
+//! [`Move`](bruteforce/choose_move/struct.Move.html) and
+//! [`MoveSelection`](bruteforce/choose_move/enum.MoveSelection.html).
 //!
 //! # Create a puzzle
 //!
-//! A binoxxo puzzle is a square board with either X or O or empty fields.
+//! A binoxxo puzzle is a (usually square) board with either X or O or empty fields.
 //! To fill the board the puzzler must fill the empty fields accourding
 //! to the binoxxo rules.
 //!
@@ -27,6 +31,59 @@
 //! It creates a [`Board`](field/struct.Board.html) struct. You can also create `Board`s
 //! manually.
 //!
+//! Boards may also be rectangular with one row per line, e.g. 6 x 4:
+//! ```
+//! use binoxxo::field::Board;
+//! use std::str::FromStr;
+//! let board = Board::from_str("
+//!     X X O X O O
+//!     X O X O X O
+//!     O X O O X X
+//!     O O X X O X"
+//! ).unwrap();
+//! assert_eq!((6, 4), (board.get_width(), board.get_height()));
+//! assert!(binoxxo::rules::is_board_valid(&board));
+//! use binoxxo::bruteforce::{BoardKind, Generator};
+//! let puzzle = Generator::new(8, 10, BoardKind::Puzzle { guesses: 15 }).build();
+//! ```
+//!
+//! [`Board::to_code`](field/struct.Board.html#method.to_code) writes a board as
+//! short share code for URLs or QR codes and
+//! [`Board::from_code`](field/struct.Board.html#method.from_code) reads it back:
+//! ```
+//! use binoxxo::field::Board;
+//! let board = binoxxo::bruteforce::create_puzzle_board(10, 15);
+//! let code = board.to_code();
+//! assert_eq!(board, Board::from_code(&code).unwrap());
+//! ```
+//!
+//! A [`Notation`](notation/struct.Notation.html) reads and writes boards with
+//! other symbols, e.g. Takuzu puzzles with `0`, `1` and `.`, rows without
+//! spaces and `#` comments:
+//! ```
+//! use binoxxo::notation::Notation;
+//! let board = Notation::takuzu().parse("01..\n..1.\n....\n...0").unwrap();
+//! println!("{}", Notation::dense().format(&board));
+//! ```
+//!
+//! [`Svg`](svg/struct.Svg.html) draws a board as SVG image for print or
+//! the web, optionally with the fields of its solution:
+//! ```
+//! use binoxxo::svg::Svg;
+//! let mut puzzle = binoxxo::bruteforce::create_puzzle_board(10, 15);
+//! puzzle.mark_given();
+//! let image = Svg::default().render(&puzzle);
+//! ```
+//!
+//! A [`Book`](book/struct.Book.html) lays out puzzles as printable HTML
+//! booklet with several grids per page and an answer key at the back:
+//! ```
+//! use binoxxo::book::{Book, Puzzle};
+//! let board = binoxxo::bruteforce::create_unique_puzzle_board(8, 0);
+//! let puzzle = Puzzle::new("Puzzle 1", board).unwrap();
+//! let html = Book::default().to_html(&[puzzle]);
+//! ```
+//!
 //! ## create_puzzle_board
 //!
 //! Use [`create_puzzle_board`](bruteforce/Board::from_str/fn.create_puzzle_board.html) to create a random puzzle:
@@ -43,6 +100,27 @@
 //! The larger `guesses` the more complicated the resulting puzzle and
 //! the more empty fields does the board has.
 //!
+//! To play the puzzle, mark its clues as given. Given fields cannot be
+//! changed by `set` or `clear` and are written with a `*` (e.g. `X*`):
+//! ```
+//! let mut board = binoxxo::bruteforce::create_puzzle_board(10, 15);
+//! board.mark_given();
+//! ```
+//!
+//! ## create_unique_puzzle_board
+//!
+//! A puzzle from `create_puzzle_board` may have more than one solution.
+//! Use [`create_unique_puzzle_board`](bruteforce/build_board/fn.create_unique_puzzle_board.html)
+//! to create a puzzle with exactly one solution:
+//! ```
+//! let size = 8usize;
+//! let clues = 0usize;
+//! let board = binoxxo::bruteforce::create_unique_puzzle_board(size, clues);
+//! assert!(binoxxo::bruteforce::has_unique_solution(&board));
+//! ```
+//! Fields are taken away until only `clues` fields are left or no more field
+//! can be taken away without allowing a second solution.
+//!
 //! ## create_full_board
 //!
 //! You can also create a randomly full board without empty fields:
@@ -54,6 +132,127 @@
 //!
 //! See the create_board example for how to print the resulting board.
 //!
+//! ## Errors instead of panics
+//!
+//! The `create_*` functions panic on odd sizes and if the search for a board
+//! fails. Use a [`Generator`](bruteforce/build_board/struct.Generator.html) to get a
+//! [`BuildError`](bruteforce/build_board/enum.BuildError.html) instead.
+//! It also limits the search with a [`Budget`](bruteforce/build_board/struct.Budget.html):
+//! ```
+//! use binoxxo::bruteforce::{BoardKind, Budget, Generator};
+//! use std::time::Duration;
+//! let budget = Budget {
+//!     max_tries: 10_000,
+//!     timeout: Some(Duration::from_secs(1)),
+//! };
+//! match Generator::square(10, BoardKind::Puzzle { guesses: 15 })
+//!     .with_budget(budget)
+//!     .build()
+//! {
+//!     Ok(board) => println!("Board:\n{}", board.to_string()),
+//!     Err(error) => println!("No board: {}", error),
+//! }
+//! ```
+//!
+//! ## Reproducible boards
+//!
+//! All `create_*` functions have a `create_*_with_rng` variant, which takes
+//! the random choices from a caller supplied random number generator.
+//! With [`rng_from_seed`](bruteforce/random/fn.rng_from_seed.html) the same
+//! seed creates the same board on every platform:
+//! ```
+//! use binoxxo::bruteforce::{create_puzzle_board_with_rng, rng_from_seed};
+//! let puzzle_number = 4711u64;
+//! let board = create_puzzle_board_with_rng(10, 15, &mut rng_from_seed(puzzle_number));
+//! println!("Puzzle #{}:\n{}", puzzle_number, board.to_string());
+//! ```
+//!
+//! # Solve a puzzle
+//!
+//! Use [`solve`](bruteforce/solver/fn.solve.html) to complete a partially filled board:
+//! ```
+//! use binoxxo::field::Board;
+//! use std::str::FromStr;
+//! let puzzle = Board::from_str("
+//!     O O _ _
+//!     O _ _ _
+//!     _ _ X _
+//!     _ _ _ _"
+//! ).unwrap();
+//! let solution = binoxxo::bruteforce::solve(&puzzle).unwrap();
+//! println!("Solution:\n{}", solution.to_string());
+//! assert!(binoxxo::bruteforce::has_unique_solution(&puzzle));
+//! ```
+//! [`count_solutions`](bruteforce/solver/fn.count_solutions.html) counts the
+//! solutions of a puzzle up to a given limit.
+//!
+//! # Solve like a human
+//!
+//! The [`logic`](logic/index.html) module solves puzzles step by step with
+//! named binoxxo techniques and reports, why a field is forced:
+//! ```
+//! use binoxxo::field::Board;
+//! use binoxxo::logic::{find_step, Technique};
+//! use std::str::FromStr;
+//! let puzzle = Board::from_str("
+//!     _ X X _
+//!     _ _ _ _
+//!     _ _ _ _
+//!     _ _ _ _"
+//! ).unwrap();
+//! let step = find_step(&puzzle).unwrap();
+//! assert_eq!(Technique::Pair, step.technique);
+//! assert_eq!((0, 0), (step.x, step.y));
+//! assert_eq!(vec![(1, 0), (2, 0)], step.reasons);
+//! ```
+//!
+//! [`next_hint`](logic/hint/fn.next_hint.html) explains the next forced field
+//! in plain words (or points at a field, which breaks a rule):
+//! ```
+//! # use binoxxo::field::Board;
+//! # use std::str::FromStr;
+//! # let puzzle = Board::from_str("_ X X _ _ _ _ _ _ _ _ _ _ _ _ _").unwrap();
+//! if let Some(hint) = binoxxo::logic::next_hint(&puzzle) {
+//!     println!("{}", hint.reason());
+//! }
+//! ```
+//!
+//! [`grade`](logic/grade/fn.grade.html) rates the difficulty of a puzzle by
+//! the hardest technique needed and whether fields must be guessed.
+//! [`grade_details`](logic/grade/fn.grade_details.html) also returns a score:
+//! ```
+//! let puzzle = binoxxo::bruteforce::create_unique_puzzle_board(6, 0);
+//! println!("{}", binoxxo::logic::grade(&puzzle));
+//! let grade = binoxxo::logic::grade_details(&puzzle).unwrap();
+//! println!("{} (score {})", grade.difficulty, grade.score);
+//! ```
+//!
+//! Use [`create_puzzle_board_with_difficulty`](logic/generate/fn.create_puzzle_board_with_difficulty.html)
+//! to create a puzzle of a given difficulty:
+//! ```
+//! use binoxxo::logic::Difficulty;
+//! let board = binoxxo::logic::create_puzzle_board_with_difficulty(6, Difficulty::Medium);
+//! assert_eq!(Difficulty::Medium, binoxxo::logic::grade(&board));
+//! ```
+//!
+//! # Play a puzzle
+//!
+//! A [`GameSession`](session/struct.GameSession.html) keeps the clues of a
+//! puzzle fixed, records the moves of the player with unlimited undo and redo
+//! and can be saved to resume the game later:
+//! ```
+//! use binoxxo::field::Field;
+//! use binoxxo::session::GameSession;
+//! let mut session = GameSession::new(binoxxo::bruteforce::create_puzzle_board(10, 15));
+//! if let Some((x, y)) = (0..100).map(|i| (i % 10, i / 10)).find(|&(x, y)| {
+//!     Field::Empty == session.board().get(x, y)
+//! }) {
+//!     session.set(x, y, Field::X).unwrap();
+//!     session.undo();
+//! }
+//! let saved = session.to_string();
+//! ```
+//!
 //! # Check a board
 //!
 //! You can check a full board with the [`is_valid_board`](bruteforce/rules/fn.is_board_valid.html):
@@ -69,6 +268,25 @@
 //! ).unwrap();
 //! println!("Board is valid: {}", is_board_valid(&board));
 //! ```
+//!
+//! [`check_board`](bruteforce/rules/fn.check_board.html) reports which rules
+//! are broken and which fields are involved:
+//! ```
+//! use binoxxo::field::Board;
+//! use binoxxo::rules::{check_board, Violation};
+//! use std::str::FromStr;
+//! let board = Board::from_str("
+//!     X X X O
+//!     O O X X
+//!     X O O X
+//!     O X O O"
+//! ).unwrap();
+//! let violations = check_board(&board);
+//! assert_eq!(Violation::Triple([(0, 0), (1, 0), (2, 0)]), violations[0]);
+//! for violation in violations {
+//!     println!("{}", violation);
+//! }
+//! ```
 //! # Rules
 //!
 //! * there must be no empty fields
@@ -86,6 +304,13 @@
 //!
 //! The crate is published under the [MIT](https://opensource.org/licenses/MIT) license.
 
+pub mod book;
 pub mod bruteforce;
+pub mod code;
 pub mod field;
+pub mod logic;
+pub mod notation;
+pub mod notes;
+pub mod session;
+pub mod svg;
 pub use bruteforce::rules;
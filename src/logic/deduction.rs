@@ -0,0 +1,533 @@
+//! This module finds logically forced fields with named binoxxo techniques
+//! (see `enum` [`Technique`](enum.Technique.html)).
+//!
+//! `fn` [`find_step`](fn.find_step.html) returns the next forced field
+//! found with the easiest technique, and `fn` [`deduce`](fn.deduce.html)
+//! fills all fields, which can be found that way.
+//!
+//! Every forced field is reported as [`Step`](struct.Step.html)
+//! with the technique, the field and the fields, which justify the step.
+//!
+//! The rules of `bruteforce::rules` (like `is_valid_pair_rule` and
+//! `is_valid_row`) only check fields, which are already set. `Balance` and
+//! `Uniqueness` have to look ahead: they ask whether, or in how many ways,
+//! the empty fields of a line can still be filled. Trying every filling
+//! with these rules takes up to 2^n board checks for a line with n empty
+//! fields, so both techniques count the fillings with the dynamic program
+//! of `count_line_completions` instead. It applies the same pair and
+//! balance rules, but only keeps the number of X and the last run of
+//! equal fields per position, which takes quadratic time.
+
+use crate::bruteforce::rules::{can_complete_line, count_line_completions, is_board_full};
+use crate::field::{Board, Field};
+
+use std::fmt;
+
+/// Named technique to find a forced field.
+/// The techniques are ordered from easiest to hardest.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Technique {
+    /// Next to two equal adjacent fields must be the other token:
+    /// `X X _` becomes `X X O`.
+    Pair,
+    /// Between two equal fields must be the other token:
+    /// `X _ X` becomes `X O X`.
+    Sandwich,
+    /// If half of a row or column has one token,
+    /// the empty fields get the other token.
+    CompleteLine,
+    /// A token, which would leave no valid way to fill the rest of
+    /// the row or column (too many equal adjacent fields or
+    /// unbalanced number of X and O), is ruled out.
+    Balance,
+    /// A token, which would only allow ways to fill the row or column,
+    /// which are equal to a complete row or column, is ruled out.
+    Uniqueness,
+}
+
+impl Technique {
+    /// All techniques from easiest to hardest.
+    pub const ALL: [Technique; 5] = [
+        Technique::Pair,
+        Technique::Sandwich,
+        Technique::CompleteLine,
+        Technique::Balance,
+        Technique::Uniqueness,
+    ];
+
+    /// Returns a human-readable name of the technique.
+    pub fn name(self) -> &'static str {
+        match self {
+            Technique::Pair => "pair",
+            Technique::Sandwich => "sandwich",
+            Technique::CompleteLine => "complete line",
+            Technique::Balance => "balance",
+            Technique::Uniqueness => "uniqueness",
+        }
+    }
+}
+
+impl fmt::Display for Technique {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A logically forced field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    /// technique, which forces the field
+    pub technique: Technique,
+    /// column of the forced field
+    pub x: usize,
+    /// row of the forced field
+    pub y: usize,
+    /// token of the forced field (either `X` or `O`)
+    pub field: Field,
+    /// fields (column, row), which justify the step
+    pub reasons: Vec<(usize, usize)>,
+}
+
+/// Result of `fn` [`deduce`](fn.deduce.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Deduction {
+    /// board with all deduced fields set
+    pub board: Board,
+    /// deduced fields in the order they were found
+    pub steps: Vec<Step>,
+}
+
+impl Deduction {
+    /// Returns whether the deduction filled all fields of the board.
+    pub fn is_solved(&self) -> bool {
+        is_board_full(&self.board)
+    }
+}
+
+type Line = Vec<(usize, usize)>;
+
+fn rows(width: usize, height: usize) -> Vec<Line> {
+    (0..height)
+        .map(|y| (0..width).map(|x| (x, y)).collect())
+        .collect()
+}
+
+fn columns(width: usize, height: usize) -> Vec<Line> {
+    (0..width)
+        .map(|x| (0..height).map(|y| (x, y)).collect())
+        .collect()
+}
+
+fn values(board: &Board, line: &[(usize, usize)]) -> Vec<Field> {
+    line.iter().map(|(x, y)| board.get(*x, *y)).collect()
+}
+
+fn other(field: Field) -> Field {
+    match field {
+        Field::X => Field::O,
+        Field::O => Field::X,
+        Field::Empty => Field::Empty,
+    }
+}
+
+fn step(
+    technique: Technique,
+    line: &[(usize, usize)],
+    index: usize,
+    field: Field,
+    reasons: Vec<(usize, usize)>,
+) -> Option<Step> {
+    let (x, y) = line[index];
+    Some(Step {
+        technique,
+        x,
+        y,
+        field,
+        reasons,
+    })
+}
+
+fn find_pair(board: &Board, line: &[(usize, usize)], _: &[Line]) -> Option<Step> {
+    let values = values(board, line);
+    for i in 0..(values.len() - 1) {
+        let current = values[i];
+        if Field::Empty == current || current != values[i + 1] {
+            continue;
+        }
+        let reasons = vec![line[i], line[i + 1]];
+        if i > 0 && Field::Empty == values[i - 1] {
+            return step(Technique::Pair, line, i - 1, other(current), reasons);
+        }
+        if i + 2 < values.len() && Field::Empty == values[i + 2] {
+            return step(Technique::Pair, line, i + 2, other(current), reasons);
+        }
+    }
+    None
+}
+
+fn find_sandwich(board: &Board, line: &[(usize, usize)], _: &[Line]) -> Option<Step> {
+    let values = values(board, line);
+    for i in 0..(values.len() - 2) {
+        let current = values[i];
+        if Field::Empty != current && Field::Empty == values[i + 1] && current == values[i + 2] {
+            let reasons = vec![line[i], line[i + 2]];
+            return step(Technique::Sandwich, line, i + 1, other(current), reasons);
+        }
+    }
+    None
+}
+
+fn find_complete_line(board: &Board, line: &[(usize, usize)], _: &[Line]) -> Option<Step> {
+    let values = values(board, line);
+    let empty = values.iter().position(|field| Field::Empty == *field)?;
+    for field in &[Field::X, Field::O] {
+        let reasons = line
+            .iter()
+            .zip(values.iter())
+            .filter(|(_, value)| *value == field)
+            .map(|(pos, _)| *pos)
+            .collect::<Vec<(usize, usize)>>();
+        if reasons.len() == values.len() / 2 {
+            return step(Technique::CompleteLine, line, empty, other(*field), reasons);
+        }
+    }
+    None
+}
+
+fn find_balance(board: &Board, line: &[(usize, usize)], _: &[Line]) -> Option<Step> {
+    let mut values = values(board, line);
+    let reasons = line
+        .iter()
+        .zip(values.iter())
+        .filter(|(_, value)| Field::Empty != **value)
+        .map(|(pos, _)| *pos)
+        .collect::<Vec<(usize, usize)>>();
+    for i in 0..values.len() {
+        if Field::Empty != values[i] {
+            continue;
+        }
+        for field in &[Field::X, Field::O] {
+            values[i] = *field;
+            let ruled_out = !can_complete_line(&values);
+            values[i] = other(*field);
+            let possible = can_complete_line(&values);
+            values[i] = Field::Empty;
+            if ruled_out && possible {
+                return step(Technique::Balance, line, i, other(*field), reasons);
+            }
+        }
+    }
+    None
+}
+
+fn find_uniqueness(board: &Board, line: &[(usize, usize)], lines: &[Line]) -> Option<Step> {
+    let mut values = values(board, line);
+    if !values.contains(&Field::Empty) {
+        return None;
+    }
+    let mut complete_lines: Vec<(&Line, Vec<Field>)> = Vec::new();
+    for other_line in lines {
+        let other_values = self::values(board, other_line);
+        if !other_values.contains(&Field::Empty)
+            && 1 == count_line_completions(&other_values)
+            && !complete_lines.iter().any(|(_, v)| *v == other_values)
+        {
+            complete_lines.push((other_line, other_values));
+        }
+    }
+
+    for i in 0..values.len() {
+        if Field::Empty != values[i] {
+            continue;
+        }
+        for field in &[Field::X, Field::O] {
+            values[i] = *field;
+            let num_completions = count_line_completions(&values);
+            let matching = complete_lines
+                .iter()
+                .filter(|(_, complete)| {
+                    values
+                        .iter()
+                        .zip(complete.iter())
+                        .all(|(value, c)| Field::Empty == *value || value == c)
+                })
+                .collect::<Vec<_>>();
+            values[i] = other(*field);
+            let possible = can_complete_line(&values);
+            values[i] = Field::Empty;
+            if 0 < num_completions && num_completions == matching.len() as u64 && possible {
+                let reasons = matching
+                    .iter()
+                    .flat_map(|(complete_line, _)| complete_line.iter().copied())
+                    .collect();
+                return step(Technique::Uniqueness, line, i, other(*field), reasons);
+            }
+        }
+    }
+    None
+}
+
+/// Returns the next forced field found with `technique` or `None`
+/// if `technique` does not find any forced field.
+///
+/// Rows are searched before columns.
+pub fn find_step_with_technique(board: &Board, technique: Technique) -> Option<Step> {
+    let find = match technique {
+        Technique::Pair => find_pair,
+        Technique::Sandwich => find_sandwich,
+        Technique::CompleteLine => find_complete_line,
+        Technique::Balance => find_balance,
+        Technique::Uniqueness => find_uniqueness,
+    };
+    let rows = rows(board.get_width(), board.get_height());
+    let columns = columns(board.get_width(), board.get_height());
+    rows.iter()
+        .map(|row| (row, &rows))
+        .chain(columns.iter().map(|column| (column, &columns)))
+        .find_map(|(line, lines)| find(board, line, lines))
+}
+
+/// Returns the next forced field of `board` found with the easiest
+/// possible technique or `None` if no technique finds a forced field.
+///
+/// The result is only meaningful if the non-empty fields of `board`
+/// do not break the binoxxo rules.
+pub fn find_step(board: &Board) -> Option<Step> {
+    Technique::ALL
+        .iter()
+        .find_map(|technique| find_step_with_technique(board, *technique))
+}
+
+/// Fills all fields of `board`, which are forced by the techniques,
+/// one after the other.
+/// It stops when the board is full or no technique finds a forced field.
+pub fn deduce(board: &Board) -> Deduction {
+    let mut board = board.clone();
+    let mut steps = Vec::new();
+
+    while let Some(step) = find_step(&board) {
+        board.set(step.x, step.y, step.field);
+        steps.push(step);
+    }
+
+    Deduction { board, steps }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bruteforce::create_unique_puzzle_board;
+    use crate::bruteforce::solver::solve;
+    use std::str::FromStr;
+
+    #[test]
+    fn pair() {
+        let board = Board::from_str(
+            "
+            _ X X _
+            _ _ _ _
+            _ _ _ _
+            _ _ _ _",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Some(Step {
+                technique: Technique::Pair,
+                x: 0,
+                y: 0,
+                field: Field::O,
+                reasons: vec![(1, 0), (2, 0)],
+            }),
+            find_step(&board)
+        );
+    }
+
+    #[test]
+    fn pair_in_column() {
+        let board = Board::from_str(
+            "
+            _ _ _ _
+            _ _ _ O
+            _ _ _ O
+            _ _ _ _",
+        )
+        .unwrap();
+
+        let step = find_step(&board).unwrap();
+
+        assert_eq!(Technique::Pair, step.technique);
+        assert_eq!((3, 0, Field::X), (step.x, step.y, step.field));
+        assert_eq!(vec![(3, 1), (3, 2)], step.reasons);
+    }
+
+    #[test]
+    fn sandwich() {
+        let board = Board::from_str(
+            "
+            _ _ _ _
+            O _ O _
+            _ _ _ _
+            _ _ _ _",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Some(Step {
+                technique: Technique::Sandwich,
+                x: 1,
+                y: 1,
+                field: Field::X,
+                reasons: vec![(0, 1), (2, 1)],
+            }),
+            find_step(&board)
+        );
+    }
+
+    #[test]
+    fn complete_line() {
+        let board = Board::from_str(
+            "
+            X O _ _ X _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _",
+        )
+        .unwrap();
+
+        assert_eq!(
+            None,
+            find_step_with_technique(&board, Technique::CompleteLine)
+        );
+
+        let board = Board::from_str(
+            "
+            X O _ X X _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _",
+        )
+        .unwrap();
+        assert_eq!(
+            Some(Step {
+                technique: Technique::CompleteLine,
+                x: 2,
+                y: 0,
+                field: Field::O,
+                reasons: vec![(0, 0), (3, 0), (4, 0)],
+            }),
+            find_step_with_technique(&board, Technique::CompleteLine)
+        );
+    }
+
+    #[test]
+    fn balance() {
+        // with X in the 2nd field, X X _ _ _ X can only be filled
+        // with a triple O O O
+        let board = Board::from_str(
+            "
+            X _ _ _ _ X
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Some(Step {
+                technique: Technique::Balance,
+                x: 1,
+                y: 0,
+                field: Field::O,
+                reasons: vec![(0, 0), (5, 0)],
+            }),
+            find_step(&board)
+        );
+    }
+
+    #[test]
+    fn uniqueness() {
+        // the 4th row can be X O O X X O or X O O X O X,
+        // but the 1st is already taken by the 1st row
+        let board = Board::from_str(
+            "
+            X O O X X O
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            X O O X _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Some(Step {
+                technique: Technique::Uniqueness,
+                x: 4,
+                y: 3,
+                field: Field::O,
+                reasons: (0..6).map(|x| (x, 0)).collect(),
+            }),
+            find_step(&board)
+        );
+    }
+
+    #[test]
+    fn no_step_on_empty_board() {
+        assert_eq!(None, find_step(&Board::new(6)));
+    }
+
+    #[test]
+    fn no_step_on_full_board() {
+        let board = Board::from_str(
+            "
+            X O X O
+            O X O X
+            X X O O
+            O O X X",
+        )
+        .unwrap();
+
+        assert_eq!(None, find_step(&board));
+    }
+
+    #[test]
+    fn deduce_unique_puzzle() {
+        let puzzle = create_unique_puzzle_board(6, 0);
+
+        let deduction = deduce(&puzzle);
+
+        let solution = solve(&puzzle).unwrap();
+        for step in &deduction.steps {
+            assert_eq!(solution.get(step.x, step.y), step.field);
+        }
+        if deduction.is_solved() {
+            assert_eq!(solution, deduction.board);
+        }
+    }
+
+    #[test]
+    fn deduce_fills_forced_fields() {
+        let board = Board::from_str(
+            "
+            X X _ _
+            _ _ _ _
+            _ _ _ _
+            _ _ _ _",
+        )
+        .unwrap();
+
+        let deduction = deduce(&board);
+
+        assert_eq!(2, deduction.steps.len());
+        assert_eq!(Field::O, deduction.board.get(2, 0));
+        assert_eq!(Field::O, deduction.board.get(3, 0));
+        assert!(!deduction.is_solved());
+    }
+}
@@ -0,0 +1,213 @@
+//! This module creates puzzles of a given difficulty level:
+//! `fn` [`create_puzzle_board_with_difficulty`](fn.create_puzzle_board_with_difficulty.html).
+//!
+//! It uses the brute force generator of the
+//! [`build_board`](../../bruteforce/build_board/index.html) module for the
+//! full boards and takes fields away as long as
+//! `fn` [`grade`](../grade/fn.grade.html) keeps the puzzle within the level.
+//!
+//! Only square boards are supported: like `create_puzzle_board` the
+//! functions take a side length `size` and size their `Budget` with
+//! `Budget::for_size`. Rectangular puzzles of a difficulty level can be
+//! picked from the puzzles of a
+//! [`Generator`](../../bruteforce/build_board/struct.Generator.html)
+//! with `grade`.
+
+use crate::bruteforce::build_board::{build_full_board_since, take_away_fields};
+use crate::bruteforce::solver::has_unique_solution;
+use crate::bruteforce::{Budget, BuildError};
+use crate::field::Board;
+use crate::logic::grade::{grade, Difficulty};
+
+use rand::{thread_rng, Rng};
+
+use std::error::Error;
+use std::fmt;
+use std::time::Instant;
+
+/// Number of full square boards tried to take fields away from by
+/// `fn` [`create_puzzle_board_with_difficulty`](fn.create_puzzle_board_with_difficulty.html).
+const MAX_DIFFICULTY_ATTEMPTS: usize = 100;
+
+/// Reasons why no puzzle of a difficulty level was created.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DifficultyError {
+    /// the brute force generator failed
+    Build(BuildError),
+    /// no puzzle of the requested difficulty was found
+    NotReached {
+        /// requested difficulty
+        difficulty: Difficulty,
+        /// number of full boards tried to take fields away from
+        attempts: usize,
+    },
+}
+
+impl From<BuildError> for DifficultyError {
+    fn from(error: BuildError) -> DifficultyError {
+        DifficultyError::Build(error)
+    }
+}
+
+impl fmt::Display for DifficultyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DifficultyError::Build(error) => error.fmt(f),
+            DifficultyError::NotReached {
+                difficulty,
+                attempts,
+            } => write!(
+                f,
+                "no {} puzzle found after {} attempts",
+                difficulty, attempts
+            ),
+        }
+    }
+}
+
+impl Error for DifficultyError {}
+
+/// Returns a square binoxxo puzzle board of side length `size` with exactly one
+/// solution and the difficulty level `difficulty`
+/// (see `fn` [`grade`](../grade/fn.grade.html)).
+///
+/// Fields are taken away from full boards as long as the puzzle keeps a
+/// unique solution and does not get harder than `difficulty`.
+/// This is repeated with new full boards until the resulting puzzle
+/// has exactly the difficulty level `difficulty`.
+///
+/// # Panics
+///
+/// Panics if `size` is odd or zero.
+///
+/// Also panics if no puzzle of the difficulty level was found.
+/// Small boards (like 4 x 4) may not have puzzles of all difficulty levels.
+pub fn create_puzzle_board_with_difficulty(size: usize, difficulty: Difficulty) -> Board {
+    create_puzzle_board_with_difficulty_with_rng(size, difficulty, &mut thread_rng())
+}
+
+/// Same as `fn` [`create_puzzle_board_with_difficulty`](fn.create_puzzle_board_with_difficulty.html),
+/// but takes all random choices from `rng`.
+///
+/// # Panics
+///
+/// See `fn` [`create_puzzle_board_with_difficulty`](fn.create_puzzle_board_with_difficulty.html).
+pub fn create_puzzle_board_with_difficulty_with_rng<R: Rng + ?Sized>(
+    size: usize,
+    difficulty: Difficulty,
+    rng: &mut R,
+) -> Board {
+    match try_create_puzzle_board_with_difficulty_with_rng(
+        size,
+        difficulty,
+        &Budget::for_size(size),
+        rng,
+    ) {
+        Ok(board) => board,
+        Err(error) => panic!(
+            "No board found for size {} with difficulty {}: {}",
+            size, difficulty, error
+        ),
+    }
+}
+
+/// Returns a square binoxxo puzzle board of side length `size` with the difficulty
+/// level `difficulty` (see `fn` [`create_puzzle_board_with_difficulty`](fn.create_puzzle_board_with_difficulty.html))
+/// or an error if `size` is invalid, the search exceeds `budget` or no puzzle
+/// of the difficulty level was found.
+///
+/// The timeout of `budget` limits the entire search.
+pub fn try_create_puzzle_board_with_difficulty(
+    size: usize,
+    difficulty: Difficulty,
+    budget: &Budget,
+) -> Result<Board, DifficultyError> {
+    try_create_puzzle_board_with_difficulty_with_rng(size, difficulty, budget, &mut thread_rng())
+}
+
+/// Same as `fn` [`try_create_puzzle_board_with_difficulty`](fn.try_create_puzzle_board_with_difficulty.html),
+/// but takes all random choices from `rng`.
+pub fn try_create_puzzle_board_with_difficulty_with_rng<R: Rng + ?Sized>(
+    size: usize,
+    difficulty: Difficulty,
+    budget: &Budget,
+    rng: &mut R,
+) -> Result<Board, DifficultyError> {
+    let start = Instant::now();
+    // every puzzle is at most expert, so there is no need to grade it
+    let is_within_difficulty = |board: &Board| {
+        has_unique_solution(board)
+            && (Difficulty::Expert == difficulty || grade(board) <= difficulty)
+    };
+
+    for _ in 0..MAX_DIFFICULTY_ATTEMPTS {
+        let board = build_full_board_since((size, size), budget, start, rng)?;
+        let board = take_away_fields(board, 0, budget, start, rng, is_within_difficulty)?;
+        if grade(&board) == difficulty {
+            return Ok(board);
+        }
+    }
+
+    Err(DifficultyError::NotReached {
+        difficulty,
+        attempts: MAX_DIFFICULTY_ATTEMPTS,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bruteforce::random::rng_from_seed;
+
+    #[test]
+    fn puzzle_board_with_difficulty() {
+        let mut rng = rng_from_seed(4711);
+        for difficulty in &[Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+            let board = create_puzzle_board_with_difficulty_with_rng(6, *difficulty, &mut rng);
+
+            assert!(has_unique_solution(&board));
+            assert_eq!(*difficulty, grade(&board));
+        }
+    }
+
+    #[test]
+    fn puzzle_board_with_difficulty_for_same_seed() {
+        assert_eq!(
+            create_puzzle_board_with_difficulty_with_rng(
+                6,
+                Difficulty::Hard,
+                &mut rng_from_seed(3)
+            ),
+            create_puzzle_board_with_difficulty_with_rng(
+                6,
+                Difficulty::Hard,
+                &mut rng_from_seed(3)
+            )
+        );
+    }
+
+    #[test]
+    fn try_create_puzzle_board_with_unreachable_difficulty() {
+        // almost all 4 x 4 puzzles can be solved without guesses
+        assert_eq!(
+            Err(DifficultyError::NotReached {
+                difficulty: Difficulty::Expert,
+                attempts: MAX_DIFFICULTY_ATTEMPTS,
+            }),
+            try_create_puzzle_board_with_difficulty_with_rng(
+                4,
+                Difficulty::Expert,
+                &Budget::for_size(4),
+                &mut rng_from_seed(1)
+            )
+        );
+    }
+
+    #[test]
+    fn try_create_puzzle_board_with_odd_size() {
+        assert_eq!(
+            Err(DifficultyError::Build(BuildError::InvalidSize(5))),
+            try_create_puzzle_board_with_difficulty(5, Difficulty::Easy, &Budget::for_size(5))
+        );
+    }
+}
@@ -0,0 +1,250 @@
+//! This module grades the difficulty of a puzzle:
+//! `fn` [`grade`](fn.grade.html) and, with the hardest technique, the
+//! number of guesses and a score, `fn` [`grade_details`](fn.grade_details.html).
+//!
+//! The puzzle is solved with the techniques of the
+//! [`deduction`](../deduction/index.html) module.
+//! Whenever no technique finds a forced field, one field is guessed:
+//! it is taken from a solution of the puzzle.
+
+use crate::bruteforce::rules::is_board_full;
+use crate::bruteforce::solver::solve;
+use crate::field::{Board, Field};
+use crate::logic::deduction::{find_step, Technique};
+
+use std::fmt;
+
+/// Difficulty level of a puzzle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Difficulty {
+    /// only needs pairs, sandwiches and complete lines
+    Easy,
+    /// needs balance counting
+    Medium,
+    /// needs the uniqueness rule
+    Hard,
+    /// needs guesses
+    Expert,
+}
+
+impl Difficulty {
+    /// All difficulty levels from easiest to hardest.
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Medium,
+        Difficulty::Hard,
+        Difficulty::Expert,
+    ];
+
+    /// Returns a human-readable name of the difficulty level.
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+            Difficulty::Expert => "expert",
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Result of `fn` [`grade_details`](fn.grade_details.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Grade {
+    /// difficulty level of the puzzle
+    pub difficulty: Difficulty,
+    /// hardest technique needed to solve the puzzle
+    /// (`None` if the puzzle is already full or can only be solved by guessing)
+    pub hardest_technique: Option<Technique>,
+    /// number of fields, which had to be guessed
+    pub guesses: usize,
+    /// numeric score: the higher the harder;
+    /// sorts puzzles of the same difficulty level
+    pub score: u32,
+}
+
+const GUESS_SCORE: u32 = 50;
+
+fn technique_score(technique: Technique) -> u32 {
+    match technique {
+        Technique::Pair => 1,
+        Technique::Sandwich => 1,
+        Technique::CompleteLine => 2,
+        Technique::Balance => 5,
+        Technique::Uniqueness => 10,
+    }
+}
+
+fn difficulty(hardest_technique: Option<Technique>, guesses: usize) -> Difficulty {
+    if 0 < guesses {
+        return Difficulty::Expert;
+    }
+    match hardest_technique {
+        None
+        | Some(Technique::Pair)
+        | Some(Technique::Sandwich)
+        | Some(Technique::CompleteLine) => Difficulty::Easy,
+        Some(Technique::Balance) => Difficulty::Medium,
+        Some(Technique::Uniqueness) => Difficulty::Hard,
+    }
+}
+
+/// Grades the difficulty of the puzzle `board` by the hardest technique
+/// needed and whether fields had to be guessed
+/// (see `fn` [`grade_details`](fn.grade_details.html)).
+///
+/// # Panics
+///
+/// Panics if `board` has no solution.
+pub fn grade(board: &Board) -> Difficulty {
+    match grade_details(board) {
+        Some(grade) => grade.difficulty,
+        None => panic!("Cannot grade a board without solution"),
+    }
+}
+
+/// Grades the puzzle `board` like `fn` [`grade`](fn.grade.html) and also
+/// returns the hardest technique, the number of guesses and a score.
+///
+/// The score adds up the costs of all steps: 1 for a pair or sandwich,
+/// 2 for a complete line, 5 for balance counting, 10 for the uniqueness rule
+/// and 50 for every guess. A guess is needed whenever no technique finds
+/// a forced field; it fills in one empty field from a solution and makes
+/// the puzzle `Expert`.
+///
+/// Returns `None` if `board` has no solution. If `board` has several
+/// solutions, the guesses are taken from one of them.
+pub fn grade_details(board: &Board) -> Option<Grade> {
+    let solution = solve(board)?;
+    let width = board.get_width();
+    let height = board.get_height();
+    let mut board = board.clone();
+    let mut hardest_technique = None;
+    let mut guesses = 0;
+    let mut score = 0;
+
+    loop {
+        while let Some(step) = find_step(&board) {
+            board.set(step.x, step.y, step.field);
+            score += technique_score(step.technique);
+            hardest_technique = hardest_technique.max(Some(step.technique));
+        }
+        if is_board_full(&board) {
+            break;
+        }
+        let (x, y) = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .find(|(x, y)| Field::Empty == board.get(*x, *y))
+            .unwrap();
+        board.set(x, y, solution.get(x, y));
+        guesses += 1;
+        score += GUESS_SCORE;
+    }
+
+    Some(Grade {
+        difficulty: difficulty(hardest_technique, guesses),
+        hardest_technique,
+        guesses,
+        score,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn grade_full_board() {
+        let board = Board::from_str(
+            "
+            X O X O
+            O X O X
+            X X O O
+            O O X X",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Some(Grade {
+                difficulty: Difficulty::Easy,
+                hardest_technique: None,
+                guesses: 0,
+                score: 0,
+            }),
+            grade_details(&board)
+        );
+    }
+
+    #[test]
+    fn grade_easy_puzzle() {
+        let board = Board::from_str(
+            "
+            X O X _
+            O X _ X
+            X _ O O
+            _ O X X",
+        )
+        .unwrap();
+
+        let grade = grade_details(&board).unwrap();
+
+        assert_eq!(Difficulty::Easy, grade.difficulty);
+        assert_eq!(0, grade.guesses);
+        assert!(grade.hardest_technique <= Some(Technique::CompleteLine));
+    }
+
+    #[test]
+    fn grade_empty_board_needs_guesses() {
+        let grade = grade_details(&Board::new(6)).unwrap();
+
+        assert_eq!(Difficulty::Expert, grade.difficulty);
+        assert!(0 < grade.guesses);
+        assert!(GUESS_SCORE <= grade.score);
+    }
+
+    #[test]
+    fn grade_returns_difficulty() {
+        assert_eq!(Difficulty::Expert, grade(&Board::new(6)));
+    }
+
+    #[test]
+    fn grade_unsolvable_board() {
+        let board = Board::from_str(
+            "
+            X _ _ X
+            _ _ _ _
+            X _ _ X
+            _ _ _ _",
+        )
+        .unwrap();
+
+        assert_eq!(None, grade_details(&board));
+    }
+
+    #[test]
+    #[should_panic]
+    fn grade_unsolvable_board_panics() {
+        grade(&Board::from_str("X X\n_ _").unwrap());
+    }
+
+    #[test]
+    fn harder_techniques_give_higher_difficulty() {
+        assert_eq!(Difficulty::Easy, difficulty(Some(Technique::Sandwich), 0));
+        assert_eq!(Difficulty::Medium, difficulty(Some(Technique::Balance), 0));
+        assert_eq!(Difficulty::Hard, difficulty(Some(Technique::Uniqueness), 0));
+        assert_eq!(Difficulty::Expert, difficulty(Some(Technique::Pair), 1));
+    }
+
+    #[test]
+    fn difficulty_is_ordered() {
+        assert!(Difficulty::Easy < Difficulty::Medium);
+        assert!(Difficulty::Medium < Difficulty::Hard);
+        assert!(Difficulty::Hard < Difficulty::Expert);
+    }
+}
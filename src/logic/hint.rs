@@ -0,0 +1,347 @@
+//! This module gives hints to players: `fn` [`next_hint`](fn.next_hint.html).
+//!
+//! A hint is either a logically forced field with a human-readable reason
+//! or, if no field is forced, a field, which breaks a binoxxo rule.
+//! Rows and columns are counted from 1 in the human-readable reasons.
+
+use crate::bruteforce::rules::{
+    is_unique_column, is_unique_row, is_valid_colum, is_valid_pair_rule, is_valid_row,
+};
+use crate::field::{Board, Field};
+use crate::logic::deduction::{find_step, Step, Technique};
+
+/// Hint returned by `fn` [`next_hint`](fn.next_hint.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Hint {
+    /// The empty field of `step` is logically forced.
+    Forced {
+        /// forced field and the technique, which forces it
+        step: Step,
+        /// human-readable reason
+        reason: String,
+    },
+    /// The field at column `x` and row `y` breaks a binoxxo rule.
+    Violation {
+        /// column of the field
+        x: usize,
+        /// row of the field
+        y: usize,
+        /// human-readable reason
+        reason: String,
+    },
+}
+
+impl Hint {
+    /// Returns the column and row of the field, the hint points at.
+    pub fn position(&self) -> (usize, usize) {
+        match self {
+            Hint::Forced { step, .. } => (step.x, step.y),
+            Hint::Violation { x, y, .. } => (*x, *y),
+        }
+    }
+
+    /// Returns the human-readable reason of the hint.
+    pub fn reason(&self) -> &str {
+        match self {
+            Hint::Forced { reason, .. } => reason,
+            Hint::Violation { reason, .. } => reason,
+        }
+    }
+}
+
+fn other(field: Field) -> Field {
+    match field {
+        Field::X => Field::O,
+        Field::O => Field::X,
+        Field::Empty => Field::Empty,
+    }
+}
+
+fn position(x: usize, y: usize) -> String {
+    format!("column {}, row {}", x + 1, y + 1)
+}
+
+/// Returns the name of the line (row or column) of `step`.
+fn line_of_step(step: &Step) -> String {
+    if step.reasons.iter().all(|(_, y)| *y == step.y) {
+        format!("row {}", step.y + 1)
+    } else {
+        format!("column {}", step.x + 1)
+    }
+}
+
+fn explain(step: &Step) -> String {
+    let field = step.field;
+    let token = other(field);
+    match step.technique {
+        Technique::Pair => {
+            let (x1, y1) = step.reasons[0];
+            let (x2, y2) = step.reasons[1];
+            format!(
+                "The two {} at {} and {} are next to each other, \
+                 so the field next to them must be {} to avoid three {} in a line.",
+                token,
+                position(x1, y1),
+                position(x2, y2),
+                field,
+                token
+            )
+        }
+        Technique::Sandwich => {
+            let (x1, y1) = step.reasons[0];
+            let (x2, y2) = step.reasons[1];
+            format!(
+                "The field between the two {} at {} and {} must be {} \
+                 to avoid three {} in a line.",
+                token,
+                position(x1, y1),
+                position(x2, y2),
+                field,
+                token
+            )
+        }
+        Technique::CompleteLine => format!(
+            "{} already has {} {}, which is half of its fields, \
+             so the remaining fields must be {}.",
+            capitalize(&line_of_step(step)),
+            step.reasons.len(),
+            token,
+            field
+        ),
+        Technique::Balance => format!(
+            "With {} at {}, {} cannot be completed without three equal fields \
+             in a line or without unequal numbers of X and O, so it must be {}.",
+            token,
+            position(step.x, step.y),
+            line_of_step(step),
+            field
+        ),
+        Technique::Uniqueness => {
+            // reasons are the fields of complete rows or columns
+            let is_row = step.reasons[0].1 == step.reasons[1].1;
+            let (line, mut other_lines) = if is_row {
+                (
+                    format!("row {}", step.y + 1),
+                    step.reasons.iter().map(|(_, y)| *y).collect::<Vec<usize>>(),
+                )
+            } else {
+                (
+                    format!("column {}", step.x + 1),
+                    step.reasons.iter().map(|(x, _)| *x).collect::<Vec<usize>>(),
+                )
+            };
+            other_lines.dedup();
+            let other_lines = other_lines
+                .iter()
+                .map(|i| format!("{} {}", if is_row { "row" } else { "column" }, i + 1))
+                .collect::<Vec<String>>()
+                .join(" or ");
+            format!(
+                "With {} at {}, {} could only be completed equal to {}, \
+                 but all rows and columns must be unique, so it must be {}.",
+                token,
+                position(step.x, step.y),
+                line,
+                other_lines,
+                field
+            )
+        }
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Returns the reason why the non-empty field at column `x` and row `y`
+/// breaks a rule or `None` if it does not break any rule.
+fn find_violation(board: &Board, x: usize, y: usize) -> Option<String> {
+    let field = board.get(x, y);
+    let at = position(x, y);
+    if !is_valid_pair_rule(board, x, y) {
+        Some(format!(
+            "The {} at {} is part of three {} in a line.",
+            field, at, field
+        ))
+    } else if !is_valid_row(board, x, y) {
+        Some(format!(
+            "Row {} has more {} than {}.",
+            y + 1,
+            field,
+            other(field)
+        ))
+    } else if !is_valid_colum(board, x, y) {
+        Some(format!(
+            "Column {} has more {} than {}.",
+            x + 1,
+            field,
+            other(field)
+        ))
+    } else if !is_unique_row(board, y) {
+        Some(format!("Row {} is equal to another row.", y + 1))
+    } else if !is_unique_column(board, x) {
+        Some(format!("Column {} is equal to another column.", x + 1))
+    } else {
+        None
+    }
+}
+
+/// Returns a hint for the next move on `board`.
+///
+/// Prefers a logically forced field found with the easiest technique
+/// (see `fn` [`find_step`](../deduction/fn.find_step.html)).
+/// If no field is forced, it points at a field, which breaks a binoxxo rule.
+/// Returns `None` if there is neither a forced field nor a rule violation.
+pub fn next_hint(board: &Board) -> Option<Hint> {
+    if let Some(step) = find_step(board) {
+        let reason = explain(&step);
+        return Some(Hint::Forced { step, reason });
+    }
+
+    let width = board.get_width();
+    (0..board.get_height())
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .filter(|(x, y)| Field::Empty != board.get(*x, *y))
+        .find_map(|(x, y)| {
+            find_violation(board, x, y).map(|reason| Hint::Violation { x, y, reason })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn hint_for_pair() {
+        let board = Board::from_str(
+            "
+            _ X X _
+            _ _ _ _
+            _ _ _ _
+            _ _ _ _",
+        )
+        .unwrap();
+
+        let hint = next_hint(&board).unwrap();
+
+        assert_eq!((0, 0), hint.position());
+        match hint {
+            Hint::Forced { step, reason } => {
+                assert_eq!(Field::O, step.field);
+                assert_eq!(
+                    "The two X at column 2, row 1 and column 3, row 1 are next to each other, \
+                     so the field next to them must be O to avoid three X in a line.",
+                    reason
+                );
+            }
+            _ => panic!("expected forced field"),
+        }
+    }
+
+    #[test]
+    fn hint_for_complete_line() {
+        let board = Board::from_str(
+            "
+            X _ O _
+            _ _ _ _
+            _ _ _ _
+            X _ _ _",
+        )
+        .unwrap();
+
+        let hint = next_hint(&board).unwrap();
+
+        assert_eq!((0, 1), hint.position());
+        assert_eq!(
+            "Column 1 already has 2 X, which is half of its fields, \
+             so the remaining fields must be O.",
+            hint.reason()
+        );
+    }
+
+    #[test]
+    fn hint_for_uniqueness() {
+        let board = Board::from_str(
+            "
+            X O O X X O
+            _ _ _ _ _ _
+            _ _ _ _ _ _
+            X O O X _ _
+            _ _ _ _ _ _
+            _ _ _ _ _ _",
+        )
+        .unwrap();
+
+        let hint = next_hint(&board).unwrap();
+
+        assert_eq!((4, 3), hint.position());
+        assert_eq!(
+            "With X at column 5, row 4, row 4 could only be completed equal to row 1, \
+             but all rows and columns must be unique, so it must be O.",
+            hint.reason()
+        );
+    }
+
+    #[test]
+    fn hint_for_violation() {
+        let board = Board::from_str(
+            "
+            X X X O
+            O O X X
+            X O O X
+            O X O O",
+        )
+        .unwrap();
+
+        let hint = next_hint(&board).unwrap();
+
+        assert_eq!(
+            Hint::Violation {
+                x: 0,
+                y: 0,
+                reason: "The X at column 1, row 1 is part of three X in a line.".to_string(),
+            },
+            hint
+        );
+    }
+
+    #[test]
+    fn hint_for_unbalanced_row() {
+        let board = Board::from_str(
+            "
+            X O X X
+            _ _ _ _
+            _ _ _ _
+            _ _ _ _",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Some(Hint::Violation {
+                x: 0,
+                y: 0,
+                reason: "Row 1 has more X than O.".to_string(),
+            }),
+            next_hint(&board)
+        );
+    }
+
+    #[test]
+    fn no_hint_for_valid_full_board() {
+        let board = Board::from_str(
+            "
+            X O X O
+            O X O X
+            X X O O
+            O O X X",
+        )
+        .unwrap();
+
+        assert_eq!(None, next_hint(&board));
+    }
+}
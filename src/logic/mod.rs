@@ -0,0 +1,19 @@
+//! This module implements a logical solver, which solves puzzles
+//! like a human would: step by step with named binoxxo techniques.
+//! It also grades the difficulty of puzzles, creates puzzles of a
+//! difficulty level and gives hints.
+//! See submodules for details.
+
+pub mod deduction;
+pub mod generate;
+pub mod grade;
+pub mod hint;
+
+pub use self::deduction::{deduce, find_step, Deduction, Step, Technique};
+pub use self::generate::{
+    create_puzzle_board_with_difficulty, create_puzzle_board_with_difficulty_with_rng,
+    try_create_puzzle_board_with_difficulty, try_create_puzzle_board_with_difficulty_with_rng,
+    DifficultyError,
+};
+pub use self::grade::{grade, grade_details, Difficulty, Grade};
+pub use self::hint::{next_hint, Hint};
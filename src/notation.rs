@@ -0,0 +1,290 @@
+//! This module implements configurable text notations of boards:
+//! `struct` [`Notation`](struct.Notation.html).
+//!
+//! Other sources write binoxxo puzzles (also known as Takuzu or Binairo)
+//! with other symbols, e.g. `0`, `1` and `.`, and often without spaces
+//! between the fields. A `Notation` reads and writes such boards:
+//!
+//! ```
+//! use binoxxo::notation::Notation;
+//! let board = Notation::takuzu().parse("
+//!     0 1 . .  # puzzle from the newspaper
+//!     . . . .
+//!     . . 1 .
+//!     . . . 0"
+//! ).unwrap();
+//! let dense = Notation::dense().with_symbols('0', '1', '.');
+//! assert_eq!("01..\n....\n..1.\n...0\n", dense.format(&board));
+//! ```
+
+use crate::field::{build_board, Board, Field, ParseBoardError, ParseErrorKind, Token};
+
+/// Symbols and layout of a text notation of boards.
+///
+/// Fields are single characters. When parsing, whitespace between fields
+/// is optional, so a notation reads both spaced and dense rows.
+/// The layout only matters for `format`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Notation {
+    /// symbol of X
+    pub x: char,
+    /// symbol of O
+    pub o: char,
+    /// symbol of an empty field
+    pub empty: char,
+    /// suffix of given fields, e.g. `*` for `X*`
+    pub given: Option<char>,
+    /// start of a comment, which runs to the end of the line
+    pub comment: Option<char>,
+    /// whether `format` writes rows without spaces between the fields
+    pub dense: bool,
+}
+
+impl Default for Notation {
+    fn default() -> Notation {
+        Notation::binoxxo()
+    }
+}
+
+impl Notation {
+    /// Notation of `Board::to_string`: `X`, `O` and `_` separated by spaces
+    /// and given fields with `*`.
+    ///
+    /// `parse` reads more than `Board::from_str`: `#` starts a comment and
+    /// the fields of a row need not be separated by spaces (e.g. `XO__`).
+    pub fn binoxxo() -> Notation {
+        Notation {
+            x: 'X',
+            o: 'O',
+            empty: '_',
+            given: Some('*'),
+            comment: Some('#'),
+            dense: false,
+        }
+    }
+
+    /// Takuzu or Binairo notation: `0` (for X), `1` (for O) and `.`
+    /// separated by spaces.
+    pub fn takuzu() -> Notation {
+        Notation::binoxxo().with_symbols('0', '1', '.')
+    }
+
+    /// Like `binoxxo`, but one row per line without spaces, e.g. `XO__`.
+    pub fn dense() -> Notation {
+        Notation {
+            dense: true,
+            ..Notation::binoxxo()
+        }
+    }
+
+    /// Returns the notation with the symbols `x`, `o` and `empty`.
+    pub fn with_symbols(self, x: char, o: char, empty: char) -> Notation {
+        Notation {
+            x,
+            o,
+            empty,
+            ..self
+        }
+    }
+
+    fn check_symbols(&self) -> Result<(), ParseBoardError> {
+        let mut symbols = vec![self.x, self.o, self.empty];
+        symbols.extend(self.given);
+        symbols.extend(self.comment);
+        let count = symbols.len();
+        symbols.sort_unstable();
+        symbols.dedup();
+        if count != symbols.len() || symbols.iter().any(|c| c.is_whitespace()) {
+            return Err(ParseBoardError::new(ParseErrorKind::InvalidNotation));
+        }
+        Ok(())
+    }
+
+    fn symbol(&self, field: Field) -> char {
+        match field {
+            Field::X => self.x,
+            Field::O => self.o,
+            Field::Empty => self.empty,
+        }
+    }
+
+    fn field(&self, symbol: char) -> Option<Field> {
+        if symbol == self.x {
+            Some(Field::X)
+        } else if symbol == self.o {
+            Some(Field::O)
+        } else if symbol == self.empty {
+            Some(Field::Empty)
+        } else {
+            None
+        }
+    }
+
+    /// Parses a board with one row per line in this notation.
+    /// Like `Board::from_str` a square board may be written on a single line.
+    pub fn parse(&self, text: &str) -> Result<Board, ParseBoardError> {
+        self.check_symbols()?;
+        let mut lines = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = match self.comment.and_then(|comment| line.find(comment)) {
+                Some(start) => &line[..start],
+                None => line,
+            };
+            // tokens are a field and an optional given suffix
+            let mut tokens: Vec<Token<String>> = Vec::new();
+            for (column, c) in line.chars().enumerate() {
+                if c.is_whitespace() {
+                    continue;
+                }
+                match tokens.last_mut() {
+                    Some(token) if Some(c) == self.given && 1 == token.text.chars().count() => {
+                        token.text.push(c)
+                    }
+                    _ => tokens.push(Token {
+                        text: c.to_string(),
+                        line: index + 1,
+                        column: column + 1,
+                    }),
+                }
+            }
+            if !tokens.is_empty() {
+                lines.push(tokens);
+            }
+        }
+        build_board(lines, |text| {
+            let mut chars = text.chars();
+            let field = self.field(chars.next()?)?;
+            Some((field, chars.next().is_some()))
+        })
+    }
+
+    /// Writes board `board` in this notation with one row per line.
+    pub fn format(&self, board: &Board) -> String {
+        let mut text = String::new();
+        for y in 0..board.get_height() {
+            for x in 0..board.get_width() {
+                if 0 < x && !self.dense {
+                    text.push(' ');
+                }
+                text.push(self.symbol(board.get(x, y)));
+                if let Some(given) = self.given.filter(|_| board.is_given(x, y)) {
+                    text.push(given);
+                }
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const BOARD: &str = "X* O _ O\n_ _ O* X\nO X _ _\n_ _ X O*\n";
+
+    #[test]
+    fn binoxxo_is_default_format() {
+        let board = Board::from_str(BOARD).unwrap();
+
+        assert_eq!(BOARD, Notation::default().format(&board));
+        assert_eq!(board, Notation::binoxxo().parse(BOARD).unwrap());
+    }
+
+    #[test]
+    fn takuzu() {
+        let board = Board::from_str(BOARD).unwrap();
+        let text = "0* 1 . 1\n. . 1* 0\n1 0 . .\n. . 0 1*\n";
+
+        assert_eq!(text, Notation::takuzu().format(&board));
+        assert_eq!(board, Notation::takuzu().parse(text).unwrap());
+    }
+
+    #[test]
+    fn dense() {
+        let board = Board::from_str(BOARD).unwrap();
+        let text = "X*O_O\n__O*X\nOX__\n__XO*\n";
+
+        assert_eq!(text, Notation::dense().format(&board));
+        assert_eq!(board, Notation::dense().parse(text).unwrap());
+        assert_eq!(board, Notation::binoxxo().parse(text).unwrap());
+    }
+
+    #[test]
+    fn binoxxo_reads_more_than_from_str() {
+        let text = "# puzzle
+X*O _ O
+_ _ O* X  # second row
+O X _ _
+_ _ X O*
+";
+
+        assert!(Board::from_str(text).is_err());
+        assert_eq!(
+            Board::from_str(BOARD).unwrap(),
+            Notation::binoxxo().parse(text).unwrap()
+        );
+    }
+
+    #[test]
+    fn dense_single_line() {
+        let board = Notation::dense().parse("XO__OX__XO__OX__").unwrap();
+
+        assert_eq!((4, 4), (board.get_width(), board.get_height()));
+    }
+
+    #[test]
+    fn comments() {
+        let board = Notation::takuzu()
+            .parse(
+                "# Binairo 4x4
+                 01..  # first row
+
+                 ....
+                 # no row
+                 ..1.
+                 ...0",
+            )
+            .unwrap();
+
+        assert_eq!(Field::X, board.get(0, 0));
+        assert_eq!(Field::O, board.get(2, 2));
+        assert_eq!(Field::X, board.get(3, 3));
+    }
+
+    #[test]
+    fn errors() {
+        let kind = |notation: Notation, text: &str| notation.parse(text).unwrap_err().kind;
+
+        assert_eq!(
+            "Unknown field '2' in line 2, column 1",
+            Notation::takuzu()
+                .parse("0 1\n2 .")
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(ParseErrorKind::OddSize, kind(Notation::dense(), "XO_\nOX_"));
+        assert_eq!(
+            "Empty field cannot be given '_*' in line 1, column 2",
+            Notation::dense().parse("X_*\nOX").unwrap_err().to_string()
+        );
+        assert_eq!(
+            ParseErrorKind::InvalidNotation,
+            kind(Notation::binoxxo().with_symbols('X', 'X', '_'), "XX\nXX")
+        );
+        assert_eq!(
+            ParseErrorKind::InvalidNotation,
+            kind(Notation::binoxxo().with_symbols('X', 'O', ' '), "XO\nOX")
+        );
+    }
+
+    #[test]
+    fn double_given_suffix() {
+        let error = Notation::dense().parse("X**\nOX").unwrap_err();
+
+        assert_eq!(ParseErrorKind::UnknownToken, error.kind);
+        assert_eq!(Some("*".to_string()), error.token);
+        assert_eq!((Some(1), Some(3)), (error.line, error.column));
+    }
+}
@@ -0,0 +1,307 @@
+//! This module implements pencil marks of a player next to a board:
+//! `struct` [`Notes`](struct.Notes.html).
+//!
+//! Notes are candidates per field (the player thinks X or O or both may
+//! go there) and pairs of fields, which must differ.
+//! [`fill_candidates`](struct.Notes.html#method.fill_candidates) fills in
+//! the candidates of all empty fields automatically.
+
+use crate::bruteforce::possible_move::{calc_possible_moves, PossibleMove};
+use crate::field::{Board, Field};
+
+use std::collections::BTreeSet;
+
+const X_CANDIDATE: u8 = 0b01;
+const O_CANDIDATE: u8 = 0b10;
+
+fn candidate_bit(field: Field) -> u8 {
+    match field {
+        Field::X => X_CANDIDATE,
+        Field::O => O_CANDIDATE,
+        Field::Empty => panic!("Empty is no candidate"),
+    }
+}
+
+/// Pencil marks of a board with `width` columns and `height` rows.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Notes {
+    width: usize,
+    height: usize,
+    /// candidate bits per field, row by row
+    candidates: Vec<u8>,
+    /// pairs of fields (column, row), which must differ, smaller field first
+    differ: BTreeSet<((usize, usize), (usize, usize))>,
+}
+
+impl Notes {
+    /// Creates empty notes for a board with `width` columns and `height` rows.
+    pub fn new(width: usize, height: usize) -> Notes {
+        Notes {
+            width,
+            height,
+            candidates: vec![0; width * height],
+            differ: BTreeSet::new(),
+        }
+    }
+
+    /// Creates empty notes for board `board`.
+    pub fn for_board(board: &Board) -> Notes {
+        Notes::new(board.get_width(), board.get_height())
+    }
+
+    /// Returns the number of columns.
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns whether the notes have neither candidates nor pairs.
+    pub fn is_empty(&self) -> bool {
+        self.candidates.iter().all(|bits| 0 == *bits) && self.differ.is_empty()
+    }
+
+    fn index(&self, x: usize, y: usize) -> usize {
+        assert!(x < self.width);
+        assert!(y < self.height);
+
+        y * self.width + x
+    }
+
+    /// Returns the candidates of field at column `x` and row `y`:
+    /// X first, then O.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `y` are out-of-bounds.
+    pub fn candidates(&self, x: usize, y: usize) -> Vec<Field> {
+        let bits = self.candidates[self.index(x, y)];
+        [Field::X, Field::O]
+            .iter()
+            .copied()
+            .filter(|field| 0 != bits & candidate_bit(*field))
+            .collect()
+    }
+
+    /// Returns whether `field` is a candidate of field at column `x` and row `y`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `y` are out-of-bounds or `field` is `Empty`.
+    pub fn has_candidate(&self, x: usize, y: usize, field: Field) -> bool {
+        0 != self.candidates[self.index(x, y)] & candidate_bit(field)
+    }
+
+    /// Adds (`true`) or removes (`false`) `field` as candidate of field
+    /// at column `x` and row `y`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `y` are out-of-bounds or `field` is `Empty`.
+    pub fn set_candidate(&mut self, x: usize, y: usize, field: Field, candidate: bool) {
+        let index = self.index(x, y);
+        if candidate {
+            self.candidates[index] |= candidate_bit(field);
+        } else {
+            self.candidates[index] &= !candidate_bit(field);
+        }
+    }
+
+    /// Adds `field` as candidate of field at column `x` and row `y`
+    /// or removes it, if it already is a candidate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `y` are out-of-bounds or `field` is `Empty`.
+    pub fn toggle_candidate(&mut self, x: usize, y: usize, field: Field) {
+        let candidate = self.has_candidate(x, y, field);
+        self.set_candidate(x, y, field, !candidate);
+    }
+
+    /// Removes all candidates of field at column `x` and row `y`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `y` are out-of-bounds.
+    pub fn clear_candidates(&mut self, x: usize, y: usize) {
+        let index = self.index(x, y);
+        self.candidates[index] = 0;
+    }
+
+    /// Replaces the candidates of all fields with the possible moves of
+    /// `board` (see `fn`
+    /// [`calc_possible_moves`](../bruteforce/possible_move/fn.calc_possible_moves.html)).
+    /// Fields, which are not `Empty`, have no candidates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `board` has other dimensions than the notes.
+    pub fn fill_candidates(&mut self, board: &Board) {
+        assert_eq!(self.width, board.get_width());
+        assert_eq!(self.height, board.get_height());
+
+        self.candidates.iter_mut().for_each(|bits| *bits = 0);
+        // fields without a possible move keep no candidates
+        for possible_move in calc_possible_moves(&mut board.clone()) {
+            let (x, y, bits) = match possible_move {
+                PossibleMove::NoMove => continue,
+                PossibleMove::OneMove(x, y, field) => (x, y, candidate_bit(field)),
+                PossibleMove::TwoMoves(x, y) => (x, y, X_CANDIDATE | O_CANDIDATE),
+            };
+            let index = self.index(x, y);
+            self.candidates[index] = bits;
+        }
+    }
+
+    /// Marks that the fields `a` and `b` (column, row) must differ.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a field is out-of-bounds or `a` equals `b`.
+    pub fn add_differ(&mut self, a: (usize, usize), b: (usize, usize)) {
+        let pair = self.pair(a, b);
+        self.differ.insert(pair);
+    }
+
+    /// Removes the mark that the fields `a` and `b` must differ.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a field is out-of-bounds or `a` equals `b`.
+    pub fn remove_differ(&mut self, a: (usize, usize), b: (usize, usize)) {
+        let pair = self.pair(a, b);
+        self.differ.remove(&pair);
+    }
+
+    /// Returns whether the fields `a` and `b` are marked to differ.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a field is out-of-bounds or `a` equals `b`.
+    pub fn must_differ(&self, a: (usize, usize), b: (usize, usize)) -> bool {
+        self.differ.contains(&self.pair(a, b))
+    }
+
+    /// Returns all pairs of fields, which must differ.
+    pub fn differ_pairs(&self) -> Vec<((usize, usize), (usize, usize))> {
+        self.differ.iter().copied().collect()
+    }
+
+    fn pair(&self, a: (usize, usize), b: (usize, usize)) -> ((usize, usize), (usize, usize)) {
+        self.index(a.0, a.1);
+        self.index(b.0, b.1);
+        assert!(a != b, "a field cannot differ from itself");
+
+        if a < b {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn candidates() {
+        let mut notes = Notes::new(4, 2);
+        assert!(notes.is_empty());
+
+        notes.set_candidate(3, 1, Field::O, true);
+        notes.toggle_candidate(3, 1, Field::X);
+
+        assert_eq!(vec![Field::X, Field::O], notes.candidates(3, 1));
+        assert!(notes.candidates(1, 1).is_empty());
+
+        notes.toggle_candidate(3, 1, Field::X);
+
+        assert_eq!(vec![Field::O], notes.candidates(3, 1));
+        assert!(!notes.has_candidate(3, 1, Field::X));
+
+        notes.clear_candidates(3, 1);
+
+        assert!(notes.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn candidate_out_of_bounds() {
+        let notes = Notes::new(2, 2);
+
+        notes.candidates(2, 0);
+    }
+
+    #[test]
+    fn fill_candidates() {
+        let board = Board::from_str(
+            "
+            X X _ _
+            _ _ _ _
+            _ _ _ _
+            _ _ _ O",
+        )
+        .unwrap();
+        let mut notes = Notes::for_board(&board);
+        notes.set_candidate(0, 0, Field::O, true);
+
+        notes.fill_candidates(&board);
+
+        assert!(notes.candidates(0, 0).is_empty());
+        assert_eq!(vec![Field::O], notes.candidates(2, 0));
+        assert_eq!(vec![Field::X, Field::O], notes.candidates(1, 1));
+    }
+
+    #[test]
+    fn fill_candidates_without_possible_move() {
+        let board = Board::from_str(
+            "
+            X X _ _
+            _ _ O _
+            _ _ O _
+            _ _ _ _",
+        )
+        .unwrap();
+        let mut notes = Notes::for_board(&board);
+        notes.set_candidate(2, 0, Field::X, true);
+
+        notes.fill_candidates(&board);
+
+        assert!(notes.candidates(2, 0).is_empty());
+        assert_eq!(vec![Field::O], notes.candidates(3, 0));
+        assert_eq!(vec![Field::X], notes.candidates(2, 3));
+    }
+
+    #[test]
+    fn differ_pairs() {
+        let mut notes = Notes::new(4, 4);
+
+        notes.add_differ((2, 0), (1, 0));
+        notes.add_differ((0, 0), (0, 3));
+
+        assert!(notes.must_differ((1, 0), (2, 0)));
+        assert!(notes.must_differ((2, 0), (1, 0)));
+        assert!(!notes.must_differ((0, 0), (1, 0)));
+        assert_eq!(
+            vec![((0, 0), (0, 3)), ((1, 0), (2, 0))],
+            notes.differ_pairs()
+        );
+
+        notes.remove_differ((1, 0), (2, 0));
+
+        assert_eq!(vec![((0, 0), (0, 3))], notes.differ_pairs());
+    }
+
+    #[test]
+    #[should_panic(expected = "a field cannot differ from itself")]
+    fn differ_from_itself() {
+        let mut notes = Notes::new(2, 2);
+
+        notes.add_differ((1, 1), (1, 1));
+    }
+}
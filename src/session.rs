@@ -0,0 +1,705 @@
+//! This module implements a game of a human player on a puzzle:
+//! `struct` [`GameSession`](struct.GameSession.html).
+//!
+//! A session records every change of the player, so changes can be undone
+//! and redone without limit and the game can return to checkpoints.
+//! Pencil marks of the player are kept as [`Notes`](../notes/struct.Notes.html).
+//! The session can be saved as text and resumed later:
+//!
+//! ```
+//! use binoxxo::field::{Board, Field};
+//! use binoxxo::session::GameSession;
+//! use std::str::FromStr;
+//! let mut session = GameSession::new(Board::from_str("X _ _ _ _ _ O _ _ _ _ _ _ O _ _").unwrap());
+//! session.set(1, 0, Field::O).unwrap();
+//! session.undo();
+//! let saved = session.to_string();
+//! let mut resumed = GameSession::from_str(&saved).unwrap();
+//! assert!(resumed.redo().is_some());
+//! assert_eq!(Field::O, resumed.board().get(1, 0));
+//! ```
+
+use crate::field::{Board, Field};
+use crate::notes::Notes;
+use crate::rules::{is_board_full, is_board_valid};
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// First line of a saved session with the version of the save format.
+const SAVE_HEADER: &str = "binoxxo session 1";
+
+/// A change of one field by the player.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Change {
+    /// column of the changed field
+    pub x: usize,
+    /// row of the changed field
+    pub y: usize,
+    /// field before the change
+    pub previous: Field,
+    /// field after the change (`Empty` if the field was cleared)
+    pub field: Field,
+}
+
+/// A position in the history of a session, see
+/// `fn` [`GameSession::add_checkpoint`](struct.GameSession.html#method.add_checkpoint).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Checkpoint(usize);
+
+/// Reasons why a session rejected a change or a saved session.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SessionError {
+    /// the field is outside of the board
+    OutOfBounds {
+        /// column of the field
+        x: usize,
+        /// row of the field
+        y: usize,
+    },
+    /// the field is a clue of the puzzle and cannot be changed
+    GivenField {
+        /// column of the field
+        x: usize,
+        /// row of the field
+        y: usize,
+    },
+    /// the checkpoint was dropped, because its changes were undone and
+    /// replaced by new changes
+    UnknownCheckpoint(Checkpoint),
+    /// the saved session cannot be read
+    InvalidSave(String),
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SessionError::OutOfBounds { x, y } => {
+                write!(f, "column {}, row {} is outside of the board", x, y)
+            }
+            SessionError::GivenField { x, y } => {
+                write!(f, "column {}, row {} is given and cannot be changed", x, y)
+            }
+            SessionError::UnknownCheckpoint(Checkpoint(position)) => {
+                write!(f, "no checkpoint after {} changes", position)
+            }
+            SessionError::InvalidSave(reason) => write!(f, "invalid saved session: {}", reason),
+        }
+    }
+}
+
+impl Error for SessionError {}
+
+/// A game of a human player: a puzzle, the current board and the history
+/// of all changes.
+///
+/// The fields of the puzzle, which are not `Empty`, are given:
+/// they cannot be changed. Undone changes can be redone until the
+/// player makes a new change.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameSession {
+    puzzle: Board,
+    board: Board,
+    /// all changes, the first `position` of them are applied to `board`
+    history: Vec<Change>,
+    position: usize,
+    /// positions in `history`, sorted
+    checkpoints: Vec<usize>,
+    notes: Notes,
+}
+
+impl GameSession {
+    /// Starts a game on `puzzle`. All fields of `puzzle`, which are not
+    /// `Empty`, become given fields.
+    pub fn new(mut puzzle: Board) -> GameSession {
+        puzzle.mark_given();
+        GameSession::with_start(puzzle)
+    }
+
+    /// Starts a game on `board` and keeps its given fields as they are:
+    /// fields, which are not `Empty` and not given, are entries of the
+    /// player and may be changed.
+    pub fn with_start(board: Board) -> GameSession {
+        GameSession {
+            notes: Notes::for_board(&board),
+            puzzle: board.clone(),
+            board,
+            history: Vec::new(),
+            position: 0,
+            checkpoints: Vec::new(),
+        }
+    }
+
+    /// Returns the board at the start of the game.
+    pub fn puzzle(&self) -> &Board {
+        &self.puzzle
+    }
+
+    /// Returns the current board.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Returns all changes applied to the current board, first change first.
+    pub fn history(&self) -> &[Change] {
+        &self.history[..self.position]
+    }
+
+    /// Sets field at column `x` and row `y` to `field`.
+    /// `Field::Empty` clears the field.
+    ///
+    /// Setting a field to its current content is no change and
+    /// is not recorded. Any other change drops all undone changes.
+    pub fn set(&mut self, x: usize, y: usize, field: Field) -> Result<(), SessionError> {
+        if x >= self.board.get_width() || y >= self.board.get_height() {
+            return Err(SessionError::OutOfBounds { x, y });
+        }
+        if self.board.is_given(x, y) {
+            return Err(SessionError::GivenField { x, y });
+        }
+        let previous = self.board.get(x, y);
+        if previous == field {
+            return Ok(());
+        }
+        let change = Change {
+            x,
+            y,
+            previous,
+            field,
+        };
+        apply(&mut self.board, x, y, field);
+        self.history.truncate(self.position);
+        self.history.push(change);
+        let position = self.position;
+        self.checkpoints
+            .retain(|checkpoint| *checkpoint <= position);
+        self.position += 1;
+        Ok(())
+    }
+
+    /// Sets field at column `x` and row `y` to `Empty`.
+    pub fn clear(&mut self, x: usize, y: usize) -> Result<(), SessionError> {
+        self.set(x, y, Field::Empty)
+    }
+
+    /// Returns whether there is a change to undo.
+    pub fn can_undo(&self) -> bool {
+        0 < self.position
+    }
+
+    /// Returns whether there is an undone change to redo.
+    pub fn can_redo(&self) -> bool {
+        self.position < self.history.len()
+    }
+
+    /// Takes back the last change and returns it.
+    pub fn undo(&mut self) -> Option<Change> {
+        if !self.can_undo() {
+            return None;
+        }
+        self.position -= 1;
+        let change = self.history[self.position];
+        apply(&mut self.board, change.x, change.y, change.previous);
+        Some(change)
+    }
+
+    /// Applies the last undone change again and returns it.
+    pub fn redo(&mut self) -> Option<Change> {
+        if !self.can_redo() {
+            return None;
+        }
+        let change = self.history[self.position];
+        apply(&mut self.board, change.x, change.y, change.field);
+        self.position += 1;
+        Some(change)
+    }
+
+    /// Remembers the current board, so the game can return to it with
+    /// `fn` [`restore_checkpoint`](#method.restore_checkpoint).
+    pub fn add_checkpoint(&mut self) -> Checkpoint {
+        if let Err(index) = self.checkpoints.binary_search(&self.position) {
+            self.checkpoints.insert(index, self.position);
+        }
+        Checkpoint(self.position)
+    }
+
+    /// Returns all checkpoints, oldest first.
+    pub fn checkpoints(&self) -> Vec<Checkpoint> {
+        self.checkpoints.iter().copied().map(Checkpoint).collect()
+    }
+
+    /// Undoes or redoes changes until the board is the board of `checkpoint`.
+    ///
+    /// Checkpoints are dropped, when the changes after them are undone and
+    /// replaced by new changes.
+    pub fn restore_checkpoint(&mut self, checkpoint: Checkpoint) -> Result<(), SessionError> {
+        let Checkpoint(position) = checkpoint;
+        if self.checkpoints.binary_search(&position).is_err() {
+            return Err(SessionError::UnknownCheckpoint(checkpoint));
+        }
+        while self.position > position {
+            self.undo();
+        }
+        while self.position < position {
+            self.redo();
+        }
+        Ok(())
+    }
+
+    /// Returns the pencil marks of the player.
+    pub fn notes(&self) -> &Notes {
+        &self.notes
+    }
+
+    /// Returns the pencil marks of the player to change them.
+    /// Changes of the notes are not recorded in the history.
+    pub fn notes_mut(&mut self) -> &mut Notes {
+        &mut self.notes
+    }
+
+    /// Fills in the candidates of all empty fields of the current board
+    /// (see `fn` [`Notes::fill_candidates`](../notes/struct.Notes.html#method.fill_candidates)).
+    pub fn fill_candidates(&mut self) {
+        self.notes.fill_candidates(&self.board);
+    }
+
+    /// Returns whether the board is full and follows all binoxxo rules.
+    pub fn is_solved(&self) -> bool {
+        is_board_full(&self.board) && is_board_valid(&self.board)
+    }
+}
+
+fn apply(board: &mut Board, x: usize, y: usize, field: Field) {
+    match field {
+        Field::Empty => board.clear(x, y),
+        field => board.set(x, y, field),
+    }
+}
+
+/// Writes the session in the save format: a header, the puzzle
+/// (given fields with `*`), an empty line and then one line per change
+/// (`<column> <row> <field>`), the number of applied changes, the
+/// checkpoints and the notes (candidates of a field and pairs of fields,
+/// which must differ), e.g.:
+///
+/// ```text
+/// binoxxo session 1
+/// X* _
+/// _ O*
+///
+/// 1 0 O
+/// position 1
+/// checkpoint 0
+/// candidates 0 1 X O
+/// differ 0 1 1 0
+/// ```
+impl fmt::Display for GameSession {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", SAVE_HEADER)?;
+        writeln!(f, "{}", self.puzzle)?;
+        for change in &self.history {
+            writeln!(f, "{} {} {}", change.x, change.y, change.field)?;
+        }
+        writeln!(f, "position {}", self.position)?;
+        for checkpoint in &self.checkpoints {
+            writeln!(f, "checkpoint {}", checkpoint)?;
+        }
+        for y in 0..self.notes.get_height() {
+            for x in 0..self.notes.get_width() {
+                let candidates = self.notes.candidates(x, y);
+                if !candidates.is_empty() {
+                    write!(f, "candidates {} {}", x, y)?;
+                    for field in candidates {
+                        write!(f, " {}", field)?;
+                    }
+                    writeln!(f)?;
+                }
+            }
+        }
+        for ((x1, y1), (x2, y2)) in self.notes.differ_pairs() {
+            writeln!(f, "differ {} {} {} {}", x1, y1, x2, y2)?;
+        }
+        Ok(())
+    }
+}
+
+fn invalid(reason: &str, line: &str) -> SessionError {
+    SessionError::InvalidSave(format!("{} in line '{}'", reason, line))
+}
+
+fn parse_number(text: Option<&str>, line: &str) -> Result<usize, SessionError> {
+    text.and_then(|text| text.parse().ok())
+        .ok_or_else(|| invalid("expected a number", line))
+}
+
+/// Parses column and row of a field on `board` from the next two `words`.
+fn parse_position<'a, I: Iterator<Item = &'a str>>(
+    words: &mut I,
+    line: &str,
+    board: &Board,
+) -> Result<(usize, usize), SessionError> {
+    let x = parse_number(words.next(), line)?;
+    let y = parse_number(words.next(), line)?;
+    if x >= board.get_width() || y >= board.get_height() {
+        return Err(SessionError::OutOfBounds { x, y });
+    }
+    Ok((x, y))
+}
+
+fn parse_field(text: &str, line: &str) -> Result<Field, SessionError> {
+    Field::from_str(text).map_err(|error| invalid(&error.to_string(), line))
+}
+
+/// Reads a session in the format written by `Display`.
+impl FromStr for GameSession {
+    type Err = SessionError;
+
+    fn from_str(s: &str) -> Result<GameSession, SessionError> {
+        let mut lines = s.lines().map(str::trim);
+        if Some(SAVE_HEADER) != lines.next() {
+            return Err(SessionError::InvalidSave(format!(
+                "first line must be '{}'",
+                SAVE_HEADER
+            )));
+        }
+        let puzzle = lines
+            .by_ref()
+            .take_while(|line| !line.is_empty())
+            .collect::<Vec<&str>>()
+            .join("\n");
+        let puzzle = Board::from_str(&puzzle)
+            .map_err(|error| SessionError::InvalidSave(error.to_string()))?;
+        let mut session = GameSession::with_start(puzzle);
+
+        let mut position = None;
+        let mut checkpoints = Vec::new();
+        for line in lines.filter(|line| !line.is_empty()) {
+            let mut words = line.split_whitespace();
+            match words.next() {
+                Some("position") => position = Some(parse_number(words.next(), line)?),
+                Some("checkpoint") => checkpoints.push(parse_number(words.next(), line)?),
+                Some("candidates") => {
+                    let (x, y) = parse_position(&mut words, line, &session.puzzle)?;
+                    for field in words {
+                        match parse_field(field, line)? {
+                            Field::Empty => return Err(invalid("Empty is no candidate", line)),
+                            field => session.notes.set_candidate(x, y, field, true),
+                        }
+                    }
+                }
+                Some("differ") => {
+                    let a = parse_position(&mut words, line, &session.puzzle)?;
+                    let b = parse_position(&mut words, line, &session.puzzle)?;
+                    if a == b {
+                        return Err(invalid("a field cannot differ from itself", line));
+                    }
+                    session.notes.add_differ(a, b);
+                }
+                x => {
+                    let x = parse_number(x, line)?;
+                    let y = parse_number(words.next(), line)?;
+                    let field = words
+                        .next()
+                        .ok_or_else(|| invalid("missing field", line))
+                        .and_then(|field| parse_field(field, line))?;
+                    let changes = session.history.len();
+                    session.set(x, y, field)?;
+                    if changes == session.history.len() {
+                        return Err(invalid("change without effect", line));
+                    }
+                }
+            }
+        }
+
+        let position = position.ok_or_else(|| {
+            SessionError::InvalidSave("missing number of applied changes".to_string())
+        })?;
+        if position > session.history.len() {
+            return Err(SessionError::InvalidSave(format!(
+                "position {} after the last change",
+                position
+            )));
+        }
+        while session.position > position {
+            session.undo();
+        }
+        for checkpoint in checkpoints {
+            if checkpoint > session.history.len() {
+                return Err(SessionError::InvalidSave(format!(
+                    "checkpoint {} after the last change",
+                    checkpoint
+                )));
+            }
+            session.checkpoints.push(checkpoint);
+        }
+        session.checkpoints.sort_unstable();
+        session.checkpoints.dedup();
+        Ok(session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> GameSession {
+        GameSession::new(
+            Board::from_str(
+                "
+                X _ _ _
+                _ _ O _
+                _ _ _ _
+                _ O _ _",
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn new_marks_given_fields() {
+        let session = session();
+
+        assert!(session.puzzle().is_given(0, 0));
+        assert!(session.board().is_given(1, 3));
+        assert!(!session.board().is_given(1, 0));
+    }
+
+    #[test]
+    fn given_fields_are_protected() {
+        let mut session = session();
+
+        assert_eq!(
+            Err(SessionError::GivenField { x: 0, y: 0 }),
+            session.set(0, 0, Field::O)
+        );
+        assert_eq!(
+            Err(SessionError::GivenField { x: 2, y: 1 }),
+            session.clear(2, 1)
+        );
+        assert_eq!(
+            Err(SessionError::OutOfBounds { x: 4, y: 0 }),
+            session.set(4, 0, Field::O)
+        );
+        assert!(session.history().is_empty());
+    }
+
+    #[test]
+    fn records_changes() {
+        let mut session = session();
+
+        session.set(1, 0, Field::O).unwrap();
+        session.set(1, 0, Field::O).unwrap();
+        session.set(1, 0, Field::X).unwrap();
+        session.clear(1, 0).unwrap();
+
+        assert_eq!(
+            vec![
+                Change {
+                    x: 1,
+                    y: 0,
+                    previous: Field::Empty,
+                    field: Field::O
+                },
+                Change {
+                    x: 1,
+                    y: 0,
+                    previous: Field::O,
+                    field: Field::X
+                },
+                Change {
+                    x: 1,
+                    y: 0,
+                    previous: Field::X,
+                    field: Field::Empty
+                },
+            ],
+            session.history()
+        );
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let mut session = session();
+        session.set(1, 0, Field::O).unwrap();
+        session.set(2, 0, Field::X).unwrap();
+
+        assert_eq!(2, session.undo().unwrap().x);
+        assert_eq!(1, session.undo().unwrap().x);
+        assert_eq!(None, session.undo());
+        assert_eq!(session.puzzle(), session.board());
+
+        assert_eq!(1, session.redo().unwrap().x);
+        assert_eq!(Field::O, session.board().get(1, 0));
+        assert!(session.can_redo());
+
+        session.set(3, 0, Field::O).unwrap();
+
+        assert!(!session.can_redo());
+        assert_eq!(None, session.redo());
+        assert_eq!(Field::Empty, session.board().get(2, 0));
+        assert_eq!(2, session.history().len());
+    }
+
+    #[test]
+    fn checkpoints() {
+        let mut session = session();
+        session.set(1, 0, Field::O).unwrap();
+        let first = session.add_checkpoint();
+        session.set(2, 0, Field::X).unwrap();
+        let second = session.add_checkpoint();
+        session.set(3, 0, Field::O).unwrap();
+
+        session.restore_checkpoint(first).unwrap();
+
+        assert_eq!(Field::Empty, session.board().get(2, 0));
+        assert_eq!(1, session.history().len());
+
+        session.restore_checkpoint(second).unwrap();
+
+        assert_eq!(Field::X, session.board().get(2, 0));
+        assert_eq!(Field::Empty, session.board().get(3, 0));
+
+        session.restore_checkpoint(first).unwrap();
+        session.set(0, 1, Field::O).unwrap();
+
+        assert_eq!(vec![first], session.checkpoints());
+        assert_eq!(
+            Err(SessionError::UnknownCheckpoint(second)),
+            session.restore_checkpoint(second)
+        );
+    }
+
+    #[test]
+    fn solved() {
+        let mut session =
+            GameSession::new(Board::from_str("X O X O O X O X X X O O O O X _").unwrap());
+        assert!(!session.is_solved());
+
+        session.set(3, 3, Field::X).unwrap();
+
+        assert!(session.is_solved());
+    }
+
+    #[test]
+    fn save_format() {
+        let mut session = session();
+        session.set(1, 0, Field::O).unwrap();
+        session.add_checkpoint();
+        session.set(2, 0, Field::X).unwrap();
+        session.clear(1, 0).unwrap();
+        session.undo();
+
+        assert_eq!(
+            "binoxxo session 1\n\
+             X* _ _ _\n\
+             _ _ O* _\n\
+             _ _ _ _\n\
+             _ O* _ _\n\
+             \n\
+             1 0 O\n\
+             2 0 X\n\
+             1 0 _\n\
+             position 2\n\
+             checkpoint 1\n",
+            session.to_string()
+        );
+    }
+
+    #[test]
+    fn save_and_resume() {
+        let mut session = session();
+        session.set(1, 0, Field::O).unwrap();
+        session.add_checkpoint();
+        session.set(2, 0, Field::X).unwrap();
+        session.clear(1, 0).unwrap();
+        session.undo();
+
+        let resumed = GameSession::from_str(&session.to_string()).unwrap();
+
+        assert_eq!(session, resumed);
+    }
+
+    #[test]
+    fn resume_with_player_entries() {
+        let session = GameSession::from_str(
+            "binoxxo session 1
+             X* O
+             _ _
+
+             0 1 O
+             position 1",
+        )
+        .unwrap();
+
+        assert!(!session.board().is_given(1, 0));
+        assert_eq!(Field::O, session.board().get(0, 1));
+    }
+
+    #[test]
+    fn fill_candidates() {
+        let mut session = session();
+        session.set(1, 0, Field::X).unwrap();
+
+        session.fill_candidates();
+
+        assert_eq!(vec![Field::O], session.notes().candidates(2, 0));
+        assert!(session.notes().candidates(1, 0).is_empty());
+        assert_eq!(1, session.history().len());
+    }
+
+    #[test]
+    fn save_and_resume_notes() {
+        let mut session = session();
+        session.notes_mut().set_candidate(1, 0, Field::O, true);
+        session.notes_mut().set_candidate(2, 2, Field::X, true);
+        session.notes_mut().set_candidate(2, 2, Field::O, true);
+        session.notes_mut().add_differ((3, 3), (3, 2));
+
+        let saved = session.to_string();
+
+        assert!(saved.ends_with(
+            "position 0\n\
+             candidates 1 0 O\n\
+             candidates 2 2 X O\n\
+             differ 3 2 3 3\n"
+        ));
+        assert_eq!(session, GameSession::from_str(&saved).unwrap());
+    }
+
+    #[test]
+    fn invalid_saves() {
+        let invalid = |text: &str| {
+            matches!(
+                GameSession::from_str(text),
+                Err(SessionError::InvalidSave(_))
+            )
+        };
+
+        assert!(invalid("X _\n_ _\n\nposition 0"));
+        assert!(invalid("binoxxo session 1\nX _ _\n\nposition 0"));
+        assert!(invalid("binoxxo session 1\nX _\n_ _\n\n1 0"));
+        assert!(invalid(
+            "binoxxo session 1\nX _\n_ _\n\n1 0 O\n1 0 O\nposition 1"
+        ));
+        assert!(invalid("binoxxo session 1\nX _\n_ _\n\n1 0 O\nposition 2"));
+        assert!(invalid("binoxxo session 1\nX _\n_ _\n\n1 0 O"));
+        assert!(invalid(
+            "binoxxo session 1\nX _\n_ _\n\nposition 0\ncheckpoint 1"
+        ));
+        assert!(invalid(
+            "binoxxo session 1\nX _\n_ _\n\nposition 0\ncandidates 0 0 _"
+        ));
+        assert!(invalid(
+            "binoxxo session 1\nX _\n_ _\n\nposition 0\ndiffer 0 0 0 0"
+        ));
+        assert_eq!(
+            Err(SessionError::GivenField { x: 0, y: 0 }),
+            GameSession::from_str("binoxxo session 1\nX* _\n_ _\n\n0 0 O\nposition 1")
+        );
+        assert_eq!(
+            Err(SessionError::OutOfBounds { x: 2, y: 0 }),
+            GameSession::from_str("binoxxo session 1\nX _\n_ _\n\nposition 0\ndiffer 0 0 2 0")
+        );
+    }
+}
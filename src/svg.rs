@@ -0,0 +1,344 @@
+//! This module draws boards as SVG images: `struct` [`Svg`](struct.Svg.html).
+//!
+//! Given fields (see [`Board::mark_given`](../field/struct.Board.html#method.mark_given))
+//! are drawn as clues, all other filled fields as entries of the player.
+//! The fields of a solution, which are empty on the board, can be drawn
+//! on top with a third style, e.g. for the answer key of a puzzle:
+//!
+//! ```
+//! use binoxxo::svg::Svg;
+//! let mut puzzle = binoxxo::bruteforce::create_puzzle_board(6, 10);
+//! puzzle.mark_given();
+//! let solution = binoxxo::bruteforce::solve(&puzzle).unwrap();
+//! let svg = Svg {
+//!     cell_size: 32.0,
+//!     font_family: "Georgia, serif".to_string(),
+//!     ..Svg::default()
+//! };
+//! let image = svg.render_with_solution(&puzzle, &solution);
+//! assert!(image.starts_with("<svg"));
+//! ```
+
+use crate::field::{Board, Field};
+
+use std::fmt::{self, Write};
+
+/// Style of the symbols in one kind of cells.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CellStyle {
+    /// color of the symbol
+    pub color: String,
+    /// background color of the cell, `None` for the background of the board
+    pub background: Option<String>,
+    /// CSS font weight of the symbol, e.g. `bold` or `normal`
+    pub font_weight: String,
+}
+
+impl CellStyle {
+    fn new(color: &str, background: Option<&str>, font_weight: &str) -> CellStyle {
+        CellStyle {
+            color: color.to_string(),
+            background: background.map(String::from),
+            font_weight: font_weight.to_string(),
+        }
+    }
+}
+
+/// Layout and styles of SVG images of boards.
+///
+/// Sizes are in SVG user units (pixels at 100 % zoom).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Svg {
+    /// width and height of one cell
+    pub cell_size: f64,
+    /// CSS font family of the symbols
+    pub font_family: String,
+    /// font size of the symbols relative to `cell_size`
+    pub font_scale: f64,
+    /// symbol of X
+    pub x: char,
+    /// symbol of O
+    pub o: char,
+    /// stroke width of the lines between the cells
+    pub grid_width: f64,
+    /// stroke width of the border around the board
+    pub border_width: f64,
+    /// color of the grid lines and the border
+    pub grid_color: String,
+    /// background color of the board
+    pub background: String,
+    /// style of given fields
+    pub clue: CellStyle,
+    /// style of the other filled fields
+    pub entry: CellStyle,
+    /// style of the solution overlay in `render_with_solution`
+    pub solution: CellStyle,
+}
+
+impl Default for Svg {
+    fn default() -> Svg {
+        Svg {
+            cell_size: 40.0,
+            font_family: "sans-serif".to_string(),
+            font_scale: 0.6,
+            x: 'X',
+            o: 'O',
+            grid_width: 1.0,
+            border_width: 3.0,
+            grid_color: "#000000".to_string(),
+            background: "#ffffff".to_string(),
+            clue: CellStyle::new("#000000", Some("#e8e8e8"), "bold"),
+            entry: CellStyle::new("#1c4f9c", None, "normal"),
+            solution: CellStyle::new("#a0a0a0", None, "normal"),
+        }
+    }
+}
+
+/// Escapes `text` for XML attributes and text.
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl Svg {
+    /// Returns the width and height of the image of a board with
+    /// `width` columns and `height` rows.
+    pub fn image_size(&self, width: usize, height: usize) -> (f64, f64) {
+        (
+            width as f64 * self.cell_size + self.border_width,
+            height as f64 * self.cell_size + self.border_width,
+        )
+    }
+
+    /// Draws board `board` as SVG image.
+    pub fn render(&self, board: &Board) -> String {
+        self.draw(board, None)
+    }
+
+    /// Draws board `board` as SVG image and the fields of `solution`,
+    /// which are empty on `board`, in the `solution` style.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `solution` has other dimensions than `board`.
+    pub fn render_with_solution(&self, board: &Board, solution: &Board) -> String {
+        assert_eq!(board.get_width(), solution.get_width());
+        assert_eq!(board.get_height(), solution.get_height());
+
+        self.draw(board, Some(solution))
+    }
+
+    fn symbol(&self, field: Field) -> Option<char> {
+        match field {
+            Field::X => Some(self.x),
+            Field::O => Some(self.o),
+            Field::Empty => None,
+        }
+    }
+
+    /// Returns the symbol and style of the field at column `x` and row `y`.
+    fn cell(
+        &self,
+        board: &Board,
+        solution: Option<&Board>,
+        x: usize,
+        y: usize,
+    ) -> Option<(char, &CellStyle)> {
+        let field = board.get(x, y);
+        if board.is_given(x, y) {
+            Some((self.symbol(field)?, &self.clue))
+        } else if Field::Empty != field {
+            Some((self.symbol(field)?, &self.entry))
+        } else {
+            Some((self.symbol(solution?.get(x, y))?, &self.solution))
+        }
+    }
+
+    fn draw(&self, board: &Board, solution: Option<&Board>) -> String {
+        let mut svg = String::new();
+        self.write(&mut svg, board, solution)
+            .expect("writing to a String cannot fail");
+        svg
+    }
+
+    fn write(&self, svg: &mut String, board: &Board, solution: Option<&Board>) -> fmt::Result {
+        let (width, height) = (board.get_width(), board.get_height());
+        let (image_width, image_height) = self.image_size(width, height);
+        // the border is centered on the outer grid lines
+        let offset = self.border_width / 2.0;
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = image_width,
+            h = image_height
+        )?;
+        writeln!(
+            svg,
+            r#"<rect width="{}" height="{}" fill="{}"/>"#,
+            image_width,
+            image_height,
+            escape(&self.background)
+        )?;
+        let mut symbols = String::new();
+        for y in 0..height {
+            for x in 0..width {
+                let (symbol, style) = match self.cell(board, solution, x, y) {
+                    Some(cell) => cell,
+                    None => continue,
+                };
+                let left = offset + x as f64 * self.cell_size;
+                let top = offset + y as f64 * self.cell_size;
+                if let Some(background) = &style.background {
+                    writeln!(
+                        svg,
+                        r#"<rect x="{}" y="{}" width="{s}" height="{s}" fill="{}"/>"#,
+                        left,
+                        top,
+                        escape(background),
+                        s = self.cell_size
+                    )?;
+                }
+                writeln!(
+                    symbols,
+                    r#"<text x="{}" y="{}" fill="{}" font-weight="{}">{}</text>"#,
+                    left + self.cell_size / 2.0,
+                    top + self.cell_size / 2.0,
+                    escape(&style.color),
+                    escape(&style.font_weight),
+                    escape(&symbol.to_string())
+                )?;
+            }
+        }
+        writeln!(
+            svg,
+            r#"<g font-family="{}" font-size="{}" text-anchor="middle" dominant-baseline="central">"#,
+            escape(&self.font_family),
+            self.cell_size * self.font_scale
+        )?;
+        svg.push_str(&symbols);
+        svg.push_str("</g>\n");
+        writeln!(
+            svg,
+            r#"<g stroke="{}" stroke-width="{}">"#,
+            escape(&self.grid_color),
+            self.grid_width
+        )?;
+        for x in 1..width {
+            let left = offset + x as f64 * self.cell_size;
+            writeln!(
+                svg,
+                r#"<line x1="{x}" y1="{}" x2="{x}" y2="{}"/>"#,
+                offset,
+                image_height - offset,
+                x = left
+            )?;
+        }
+        for y in 1..height {
+            let top = offset + y as f64 * self.cell_size;
+            writeln!(
+                svg,
+                r#"<line x1="{}" y1="{y}" x2="{}" y2="{y}"/>"#,
+                offset,
+                image_width - offset,
+                y = top
+            )?;
+        }
+        svg.push_str("</g>\n");
+        writeln!(
+            svg,
+            r#"<rect x="{o}" y="{o}" width="{}" height="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+            image_width - self.border_width,
+            image_height - self.border_width,
+            escape(&self.grid_color),
+            self.border_width,
+            o = offset
+        )?;
+        svg.push_str("</svg>\n");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn texts(svg: &str) -> Vec<&str> {
+        svg.lines()
+            .filter(|line| line.starts_with("<text"))
+            .collect()
+    }
+
+    #[test]
+    fn render_small_board() {
+        let board = Board::from_str("X* _\n_ O").unwrap();
+        let svg = Svg {
+            cell_size: 10.0,
+            border_width: 2.0,
+            ..Svg::default()
+        };
+
+        let image = svg.render(&board);
+
+        assert!(image.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="22" height="22" viewBox="0 0 22 22">"#
+        ));
+        assert!(image.ends_with("</svg>\n"));
+        assert_eq!(
+            vec![
+                r##"<text x="6" y="6" fill="#000000" font-weight="bold">X</text>"##,
+                r##"<text x="16" y="16" fill="#1c4f9c" font-weight="normal">O</text>"##,
+            ],
+            texts(&image)
+        );
+        assert!(image.contains(r##"<rect x="1" y="1" width="10" height="10" fill="#e8e8e8"/>"##));
+        assert!(image.contains(r#"<line x1="11" y1="1" x2="11" y2="21"/>"#));
+        assert!(image.contains(r#"<line x1="1" y1="11" x2="21" y2="11"/>"#));
+        assert!(image.contains(r#"font-size="6""#));
+    }
+
+    #[test]
+    fn solution_overlay() {
+        let board = Board::from_str("X* _\n_ O").unwrap();
+        let solution = Board::from_str("X O\nX O").unwrap();
+        let svg = Svg {
+            x: '0',
+            o: '1',
+            ..Svg::default()
+        };
+
+        let image = svg.render_with_solution(&board, &solution);
+        let texts = texts(&image);
+
+        assert_eq!(4, texts.len());
+        assert!(texts[0].ends_with(r#"font-weight="bold">0</text>"#));
+        assert!(texts[1].contains(r##"fill="#a0a0a0""##));
+        assert!(texts[1].ends_with(">1</text>"));
+        assert!(texts[2].contains(r##"fill="#a0a0a0""##));
+        assert!(texts[3].contains(r##"fill="#1c4f9c""##));
+    }
+
+    #[test]
+    #[should_panic]
+    fn solution_of_other_size() {
+        let board = Board::from_str("X _\n_ O").unwrap();
+        let solution = Board::from_str("X O X O\nO X O X").unwrap();
+
+        Svg::default().render_with_solution(&board, &solution);
+    }
+
+    #[test]
+    fn escapes_styles() {
+        let svg = Svg {
+            font_family: r#""Comic Sans" & <friends>"#.to_string(),
+            ..Svg::default()
+        };
+
+        let image = svg.render(&Board::from_str("_ _\n_ _").unwrap());
+
+        assert!(image.contains(r#"font-family="&quot;Comic Sans&quot; &amp; &lt;friends&gt;""#));
+        assert!(texts(&image).is_empty());
+    }
+}
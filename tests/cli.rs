@@ -0,0 +1,249 @@
+//! Tests of the `binoxxo` command-line tool.
+use binoxxo::field::Board;
+use binoxxo::rules::{is_board_full, is_board_valid};
+use serde_json::{json, Value};
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+use std::str::FromStr;
+
+fn binoxxo(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_binoxxo"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stdout_json(output: &Output) -> Value {
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+const PUZZLE: &str = "
+O O _ _
+O _ _ _
+_ _ X _
+_ _ _ _
+";
+
+#[test]
+fn generate_is_reproducible() {
+    let args = ["generate", "--size", "6", "--seed", "4711", "--count", "2"];
+    let first = binoxxo(&args, "");
+    let second = binoxxo(&args, "");
+
+    assert!(first.status.success());
+    assert_eq!(stdout(&first), stdout(&second));
+    let boards = stdout(&first)
+        .split("\n\n")
+        .map(|text| Board::from_str(text).unwrap())
+        .collect::<Vec<Board>>();
+    assert_eq!(2, boards.len());
+    assert_eq!((6, 6), (boards[0].get_width(), boards[0].get_height()));
+}
+
+#[test]
+fn generate_full_rectangular_board() {
+    let output = binoxxo(&["generate", "--full", "--width", "8", "--height", "6"], "");
+
+    let board = Board::from_str(&stdout(&output)).unwrap();
+    assert_eq!((8, 6), (board.get_width(), board.get_height()));
+    assert!(is_board_full(&board));
+    assert!(is_board_valid(&board));
+}
+
+#[test]
+fn solve_puzzle() {
+    let output = binoxxo(&["solve"], PUZZLE);
+
+    assert!(output.status.success());
+    assert_eq!("O O X X\nO X O X\nX O X O\nX X O O\n", stdout(&output));
+}
+
+#[test]
+fn solve_reads_board_from_str_format() {
+    let output = binoxxo(&["solve"], &format!("# from a newspaper\n{}", PUZZLE));
+
+    assert_eq!(Some(2), output.status.code());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("board 1: "));
+}
+
+#[test]
+fn solve_binoxxo_notation_with_comments() {
+    let input = format!("# from a newspaper\n{}", PUZZLE.replace(' ', ""));
+
+    let output = binoxxo(&["solve", "--notation", "binoxxo"], &input);
+
+    assert!(output.status.success());
+    assert_eq!("O O X X\nO X O X\nX O X O\nX X O O\n", stdout(&output));
+}
+
+#[test]
+fn solve_takuzu_notation() {
+    let input = format!("# from a newspaper\n\n{}", PUZZLE)
+        .replace('X', "0")
+        .replace('O', "1")
+        .replace('_', ".");
+
+    let output = binoxxo(&["solve", "--notation", "takuzu"], &input);
+
+    assert!(output.status.success());
+    assert_eq!("1 1 0 0\n1 0 1 0\n0 1 0 1\n0 0 1 1\n", stdout(&output));
+}
+
+#[test]
+fn solve_dense_notation() {
+    let output = binoxxo(&["solve", "--notation", "dense"], &PUZZLE.replace(' ', ""));
+
+    assert!(output.status.success());
+    assert_eq!("OOXX\nOXOX\nXOXO\nXXOO\n", stdout(&output));
+}
+
+#[test]
+fn solve_several_puzzles_as_json() {
+    let input = format!("{}\nX _ _ X\n_ _ _ _\nX _ _ X\n_ _ _ _\n", PUZZLE);
+
+    let output = binoxxo(&["solve", "--format", "json"], &input);
+
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!(
+        json!([
+            {"solution": ["O O X X", "O X O X", "X O X O", "X X O O"], "unique": true},
+            {"solution": null, "unique": false},
+        ]),
+        stdout_json(&output)
+    );
+}
+
+#[test]
+fn check_reports_violations() {
+    let output = binoxxo(&["check"], "X X X O\nO O X X\nX O O X\nO X O O\n");
+
+    assert_eq!(Some(1), output.status.code());
+    assert!(stdout(&output)
+        .starts_with("Three equal fields in a line from column 1, row 1 to column 3, row 1.\n"));
+}
+
+#[test]
+fn check_valid_board() {
+    let output = binoxxo(&["check"], "X O X O\nO X O X\nX X O O\nO O X X\n");
+
+    assert!(output.status.success());
+    assert_eq!("valid\n", stdout(&output));
+}
+
+#[test]
+fn grade_puzzle() {
+    let output = binoxxo(&["grade", "--format", "json"], PUZZLE);
+
+    assert!(output.status.success());
+    assert!(stdout_json(&output)[0]["difficulty"].is_string());
+}
+
+#[test]
+fn grade_solved_board_without_technique() {
+    let output = binoxxo(
+        &["grade", "--format", "json"],
+        "X O X O\nO X O X\nX X O O\nO O X X\n",
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        json!([{"difficulty": "easy", "score": 0, "hardest_technique": null, "guesses": 0}]),
+        stdout_json(&output)
+    );
+}
+
+#[test]
+fn hint_for_puzzle() {
+    let output = binoxxo(&["hint"], "_ X X _\n_ _ _ _\n_ _ _ _\n_ _ _ _\n");
+
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("column 1, row 1: The two X at column 2, row 1"));
+}
+
+#[test]
+fn book_of_generated_puzzles() {
+    let puzzles = stdout(&binoxxo(
+        &["generate", "--size", "6", "--seed", "4711", "--count", "3"],
+        "",
+    ));
+
+    let output = binoxxo(&["book", "--title", "Week 42"], &puzzles);
+
+    assert!(output.status.success());
+    let html = stdout(&output);
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<title>Week 42</title>"));
+    assert!(html.contains("Puzzle 3"));
+    // 3 puzzles and 3 solutions
+    assert_eq!(6, html.matches("<svg").count());
+}
+
+#[test]
+fn book_needs_solvable_puzzles() {
+    let output = binoxxo(&["book"], "X X\n_ _\n");
+
+    assert_eq!(Some(2), output.status.code());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("board 1: no unique solution"));
+}
+
+#[test]
+fn invalid_arguments() {
+    assert_eq!(Some(2), binoxxo(&["unknown"], "").status.code());
+    assert_eq!(Some(2), binoxxo(&["generate", "--size"], "").status.code());
+    assert_eq!(
+        Some(2),
+        binoxxo(&["generate", "--full", "--guesses", "3"], "")
+            .status
+            .code()
+    );
+    assert_eq!(
+        Some(2),
+        binoxxo(
+            &[
+                "generate",
+                "--difficulty",
+                "easy",
+                "--width",
+                "6",
+                "--height",
+                "4"
+            ],
+            ""
+        )
+        .status
+        .code()
+    );
+    assert_eq!(Some(2), binoxxo(&["solve"], "X O\nO").status.code());
+}
+
+#[test]
+#[cfg(not(feature = "tui"))]
+fn play_needs_tui_feature() {
+    let output = binoxxo(&["play"], "");
+
+    assert_eq!(Some(2), output.status.code());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--features tui"));
+}
+
+#[test]
+#[cfg(feature = "tui")]
+fn play_rejects_clues() {
+    let output = binoxxo(&["play", "--clues", "0"], "");
+
+    assert_eq!(Some(2), output.status.code());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("play only supports --guesses"));
+}
@@ -1,7 +1,7 @@
 //! Regression test for #1
 //! Based on check_board example
 use binoxxo::field::*;
-use binoxxo::rules::{is_board_full, is_board_valid};
+use binoxxo::rules::{check_board, is_board_full, is_board_valid, Violation};
 use std::str::FromStr;
 
 #[test]
@@ -24,3 +24,28 @@ fn issue_1() {
     assert!(is_board_full(&ok));
     assert!(!is_board_valid(&ok));
 }
+
+#[test]
+fn issue_1_violations() {
+    let ok = Board::from_str(
+        "O O X O O X X O X X
+         X O O X X O O X X O
+         O X O X O X X O O X
+         O X X O O X O X O X
+         X O X O X O X O X O
+         O X O X X O X X O O
+         O X X O O X O O X X
+         X O O X O X O X X O
+         X O X O X O X O O X
+         X X O X X O O X O O
+        ",
+    )
+    .unwrap();
+
+    let violations = check_board(&ok);
+
+    assert_eq!(
+        vec![Violation::Triple([(0, 7), (0, 8), (0, 9)])],
+        violations
+    );
+}
@@ -0,0 +1,63 @@
+//! The same seed must create the same boards on every platform
+//! and in every release.
+//! If one of these tests fails, published puzzle numbers become invalid.
+use binoxxo::bruteforce::{
+    create_full_board_with_rng, create_puzzle_board_with_rng, create_unique_puzzle_board_with_rng,
+    rng_from_seed,
+};
+use binoxxo::field::Board;
+use std::str::FromStr;
+
+#[test]
+fn seeded_full_board() {
+    let expected = Board::from_str(
+        "X X O X O O
+         X O X O O X
+         O X O X X O
+         O O X O X X
+         X O O X O X
+         O X X O X O",
+    )
+    .unwrap();
+
+    assert_eq!(
+        expected,
+        create_full_board_with_rng(6, &mut rng_from_seed(4711))
+    );
+}
+
+#[test]
+fn seeded_puzzle_board() {
+    let expected = Board::from_str(
+        "X _ _ _ _ O
+         _ O X O O _
+         _ _ _ _ _ O
+         O O _ _ _ _
+         X O O X O X
+         _ _ _ _ _ _",
+    )
+    .unwrap();
+
+    assert_eq!(
+        expected,
+        create_puzzle_board_with_rng(6, 3, &mut rng_from_seed(4711))
+    );
+}
+
+#[test]
+fn seeded_unique_puzzle_board() {
+    let expected = Board::from_str(
+        "_ _ _ _ O O
+         _ _ _ _ _ X
+         _ _ _ X _ _
+         O _ _ _ _ _
+         _ O O _ _ _
+         _ X X _ _ _",
+    )
+    .unwrap();
+
+    assert_eq!(
+        expected,
+        create_unique_puzzle_board_with_rng(6, 0, &mut rng_from_seed(4711))
+    );
+}